@@ -45,9 +45,9 @@ impl State for Example {
         let sphere_verts = gen_sphere(32, 32);
         assets.register_asset::<Mesh>();
         assets.register_asset::<Texture>();
-        assets.load_asset_from_data::<Mesh, Vec<VertexPosNormal>>("sphere", sphere_verts);
-        assets.load_asset_from_data::<Texture, [f32; 4]>("blue", [0.0, 0.0, 1.0, 1.0]);
-        assets.load_asset_from_data::<Texture, [f32; 4]>("white", [1.0, 1.0, 1.0, 1.0]);
+        assets.load_asset_from_data::<Mesh, Vec<VertexPosNormal>>("sphere", sphere_verts).unwrap();
+        assets.load_asset_from_data::<Texture, [f32; 4]>("blue", [0.0, 0.0, 1.0, 1.0]).unwrap();
+        assets.load_asset_from_data::<Texture, [f32; 4]>("white", [1.0, 1.0, 1.0, 1.0]).unwrap();
 
         let sphere = assets.create_renderable("sphere", "blue", "white", "white", 1.0).unwrap();
         world.create_now()
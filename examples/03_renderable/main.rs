@@ -97,21 +97,21 @@ impl State for Example {
         assets.register_store(DirectoryStore::new(assets_path));
 
         // Create some basic colors and load textures
-        assets.load_asset_from_data::<Texture, [f32; 4]>("red", [0.8, 0.2, 0.2, 1.0]);
-        assets.load_asset_from_data::<Texture, [f32; 4]>("green", [0.2, 0.8, 0.2, 1.0]);
-        assets.load_asset_from_data::<Texture, [f32; 4]>("blue", [0.2, 0.2, 0.8, 1.0]);
-        assets.load_asset_from_data::<Texture, [f32; 4]>("pink", [1.0, 0.8, 0.8, 1.0]);
-        assets.load_asset_from_data::<Texture, [f32; 4]>("black", [0.0, 0.0, 0.0, 1.0]);
-        assets.load_asset_from_data::<Texture, [f32; 4]>("white", [1.0, 1.0, 1.0, 1.0]);
-        assets.load_asset::<Texture>("logo", "png");
-        assets.load_asset::<Texture>("ground", "dds");
+        assets.load_asset_from_data::<Texture, [f32; 4]>("red", [0.8, 0.2, 0.2, 1.0]).unwrap();
+        assets.load_asset_from_data::<Texture, [f32; 4]>("green", [0.2, 0.8, 0.2, 1.0]).unwrap();
+        assets.load_asset_from_data::<Texture, [f32; 4]>("blue", [0.2, 0.2, 0.8, 1.0]).unwrap();
+        assets.load_asset_from_data::<Texture, [f32; 4]>("pink", [1.0, 0.8, 0.8, 1.0]).unwrap();
+        assets.load_asset_from_data::<Texture, [f32; 4]>("black", [0.0, 0.0, 0.0, 1.0]).unwrap();
+        assets.load_asset_from_data::<Texture, [f32; 4]>("white", [1.0, 1.0, 1.0, 1.0]).unwrap();
+        assets.load_asset::<Texture>("logo", "png").unwrap();
+        assets.load_asset::<Texture>("ground", "dds").unwrap();
 
         // Load/generate meshes
-        assets.load_asset::<Mesh>("teapot", "obj");
-        assets.load_asset::<Mesh>("lid", "obj");
-        assets.load_asset::<Mesh>("rectangle", "obj");
-        assets.load_asset::<Mesh>("cube", "obj");
-        assets.load_asset::<Mesh>("cone", "obj");
+        assets.load_asset::<Mesh>("teapot", "obj").unwrap();
+        assets.load_asset::<Mesh>("lid", "obj").unwrap();
+        assets.load_asset::<Mesh>("rectangle", "obj").unwrap();
+        assets.load_asset::<Mesh>("cube", "obj").unwrap();
+        assets.load_asset::<Mesh>("cone", "obj").unwrap();
 
         // Add teapot and lid to scene
         for mesh in vec!["lid", "teapot"].iter() {
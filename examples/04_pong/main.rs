@@ -1,6 +1,6 @@
 extern crate amethyst;
 
-use amethyst::{Application, Event, State, Trans, VirtualKeyCode, WindowEvent};
+use amethyst::{Application, Event, Float, State, Trans, VirtualKeyCode, WindowEvent};
 use amethyst::asset_manager::AssetManager;
 use amethyst::config::Element;
 use amethyst::ecs::{World, Join, VecStorage, Component, RunArg, System};
@@ -129,7 +129,7 @@ impl System<()> for PongSystem {
                         }
                     }
                     // Set translation[0] of renderable corresponding to this plank
-                    local.translation[0] = left_bound + plank.dimensions[0] / 2.0
+                    local.translation[0] = (left_bound + plank.dimensions[0] / 2.0) as Float
                 }
                 // If it is a right plank
                 Side::Right => {
@@ -150,13 +150,13 @@ impl System<()> for PongSystem {
                         }
                     }
                     // Set translation[0] of renderable corresponding to this plank
-                    local.translation[0] = right_bound - plank.dimensions[0] / 2.0
+                    local.translation[0] = (right_bound - plank.dimensions[0] / 2.0) as Float
                 }
             };
             // Set translation[1] of renderable corresponding to this plank
-            local.translation[1] = plank.position;
+            local.translation[1] = plank.position as Float;
             // Set scale for renderable corresponding to this plank
-            local.scale = [plank.dimensions[0], plank.dimensions[1], 1.0];
+            local.scale = [plank.dimensions[0] as Float, plank.dimensions[1] as Float, 1.0];
         }
 
         // Process the ball
@@ -216,10 +216,10 @@ impl System<()> for PongSystem {
             }
 
             // Update the renderable corresponding to this ball
-            local.translation[0] = ball.position[0];
-            local.translation[1] = ball.position[1];
-            local.scale[0] = ball.size;
-            local.scale[1] = ball.size;
+            local.translation[0] = ball.position[0] as Float;
+            local.translation[1] = ball.position[1] as Float;
+            local.scale[0] = ball.size as Float;
+            local.scale[1] = ball.size as Float;
         }
     }
 }
@@ -267,9 +267,9 @@ impl State for Pong {
         // Generate a square mesh
         assets.register_asset::<Mesh>();
         assets.register_asset::<Texture>();
-        assets.load_asset_from_data::<Texture, [f32; 4]>("white", [1.0, 1.0, 1.0, 1.0]);
+        assets.load_asset_from_data::<Texture, [f32; 4]>("white", [1.0, 1.0, 1.0, 1.0]).unwrap();
         let square_verts = gen_rectangle(1.0, 1.0);
-        assets.load_asset_from_data::<Mesh, Vec<VertexPosNormal>>("square", square_verts);
+        assets.load_asset_from_data::<Mesh, Vec<VertexPosNormal>>("square", square_verts).unwrap();
         let square = assets.create_renderable("square", "white", "white", "white", 1.0).unwrap();
 
         // Create a ball entity
@@ -5,7 +5,7 @@ extern crate amethyst;
 extern crate cgmath;
 
 use amethyst::{Application, Event, State, Trans, VirtualKeyCode, WindowEvent};
-use amethyst::asset_manager::{AssetLoader, AssetLoaderRaw, AssetManager, Assets, DirectoryStore};
+use amethyst::asset_manager::{AssetLoader, AssetLoaderRaw, AssetManager, Assets, DirectoryStore, LoaderError};
 use amethyst::config::Element;
 use amethyst::ecs::World;
 use amethyst::ecs::components::{LocalTransform, Mesh, Texture, Transform};
@@ -25,8 +25,8 @@ struct CustomObj {
 }
 
 impl AssetLoaderRaw for CustomObj {
-    fn from_raw(_: &Assets, data: &[u8]) -> Option<CustomObj> {
-        let data: String = str::from_utf8(data).unwrap().into();
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<CustomObj, LoaderError> {
+        let data: String = str::from_utf8(data)?.into();
         let mut vertices = Vec::new();
         let mut normals = Vec::new();
 
@@ -46,7 +46,7 @@ impl AssetLoaderRaw for CustomObj {
                           nums[5].parse::<f32>().unwrap()]);
         }
 
-        Some(CustomObj {
+        Ok(CustomObj {
             vertices: vertices,
             normals: normals,
         })
@@ -54,7 +54,7 @@ impl AssetLoaderRaw for CustomObj {
 }
 
 impl AssetLoader<Mesh> for CustomObj {
-    fn from_data(assets: &mut Assets, obj: CustomObj) -> Option<Mesh> {
+    fn from_data(assets: &mut Assets, obj: CustomObj) -> Result<Mesh, LoaderError> {
         let vertices = obj.vertices
             .iter()
             .zip(obj.normals.iter())
@@ -96,22 +96,22 @@ impl State for Example {
 
         // Create some basic colors for the teapot, and load some textures
         // for the cube and sphere.
-        assets.load_asset_from_data::<Texture, [f32; 4]>("dark_blue", [0.0, 0.0, 0.1, 1.0]);
-        assets.load_asset_from_data::<Texture, [f32; 4]>("green", [0.0, 1.0, 0.2, 1.0]);
-        assets.load_asset_from_data::<Texture, [f32; 4]>("tan", [0.8, 0.6, 0.5, 1.0]);
-        assets.load_asset_from_data::<Texture, [f32; 4]>("white", [1.0, 1.0, 1.0, 1.0]);
-        assets.load_asset::<Texture>("crate", "png");
-        assets.load_asset::<Texture>("grass", "bmp");
+        assets.load_asset_from_data::<Texture, [f32; 4]>("dark_blue", [0.0, 0.0, 0.1, 1.0]).unwrap();
+        assets.load_asset_from_data::<Texture, [f32; 4]>("green", [0.0, 1.0, 0.2, 1.0]).unwrap();
+        assets.load_asset_from_data::<Texture, [f32; 4]>("tan", [0.8, 0.6, 0.5, 1.0]).unwrap();
+        assets.load_asset_from_data::<Texture, [f32; 4]>("white", [1.0, 1.0, 1.0, 1.0]).unwrap();
+        assets.load_asset::<Texture>("crate", "png").unwrap();
+        assets.load_asset::<Texture>("grass", "bmp").unwrap();
 
         // Load/generate meshes
-        assets.load_asset::<Mesh>("teapot", "obj");
-        assets.load_asset::<Mesh>("lid", "obj");
-        assets.load_asset::<Mesh>("cube", "obj");
-        assets.load_asset::<Mesh>("sphere", "obj");
+        assets.load_asset::<Mesh>("teapot", "obj").unwrap();
+        assets.load_asset::<Mesh>("lid", "obj").unwrap();
+        assets.load_asset::<Mesh>("cube", "obj").unwrap();
+        assets.load_asset::<Mesh>("sphere", "obj").unwrap();
 
         // Also add custom asset loader and load mesh
         assets.register_loader::<Mesh, CustomObj>("custom");
-        assets.load_asset::<Mesh>("cuboid", "custom");
+        assets.load_asset::<Mesh>("cuboid", "custom").unwrap();
 
         // Add teapot and lid to scene
         for mesh in vec!["lid", "teapot"].iter() {
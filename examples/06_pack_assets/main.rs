@@ -0,0 +1,46 @@
+//! Packs an asset directory into a single pack file, which can be loaded with
+//! a `PackStore`.
+//!
+//! Usage: `cargo run --example pack_assets -- <asset dir> <pack file> [zstd|lz4]`
+
+extern crate amethyst;
+
+use amethyst::asset_manager::{Compression, DirectoryStore};
+use amethyst::asset_manager::pack::write_pack;
+use std::env;
+use std::fs::File;
+use std::io::BufWriter;
+use std::process;
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    if args.len() < 3 || args.len() > 4 {
+        println!("Usage: {} <asset dir> <pack file> [zstd|lz4]", args[0]);
+        process::exit(1);
+    }
+
+    let compression = match args.get(3).map(|arg| arg.as_str()) {
+        None => None,
+        Some("zstd") => Some(Compression::Zstd),
+        Some("lz4") => Some(Compression::Lz4),
+        Some(other) => {
+            println!("Unknown compression method: {}", other);
+            process::exit(1);
+        }
+    };
+
+    let store = DirectoryStore::new(&args[1]);
+    let out = File::create(&args[2]).expect("Failed to create pack file");
+    match write_pack(&store, compression, BufWriter::new(out)) {
+        Ok(entries) => {
+            for entry in &entries {
+                println!("{} ({} bytes)", entry.name, entry.len);
+            }
+            println!("Packed {} assets into {}", entries.len(), args[2]);
+        }
+        Err(e) => {
+            println!("Failed to pack assets: {}", e);
+            process::exit(1);
+        }
+    }
+}
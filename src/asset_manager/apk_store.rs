@@ -0,0 +1,52 @@
+//! Asset store reading from the assets of an Android APK.
+
+use android_glue;
+
+use asset_manager::{AssetStore, AssetStoreError};
+
+/// Asset store reading assets packaged into the `assets` directory of the
+/// game's APK through the Android asset manager, so they don't have to be
+/// extracted to disk first.
+///
+/// Assets are looked up as `<root>/<name>.<type>`. Checking for an asset
+/// reads it completely, as the asset manager offers no cheaper way.
+pub struct ApkStore {
+    root: String,
+}
+
+impl ApkStore {
+    /// Creates a new APK store for the given directory inside the APK's
+    /// `assets` directory, or `""` for the whole `assets` directory.
+    pub fn new(root: &str) -> ApkStore {
+        ApkStore { root: root.trim_matches('/').into() }
+    }
+
+    fn asset_path(&self, name: &str, asset_type: &str) -> String {
+        if self.root.is_empty() {
+            format!("{}.{}", name, asset_type)
+        } else {
+            format!("{}/{}.{}", self.root, name, asset_type)
+        }
+    }
+}
+
+impl AssetStore for ApkStore {
+    fn name(&self) -> String {
+        format!("APK assets \"{}\"", self.root)
+    }
+
+    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
+        android_glue::load_asset(&self.asset_path(name, asset_type)).is_ok()
+    }
+
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+        match android_glue::load_asset(&self.asset_path(name, asset_type)) {
+            Ok(data) => {
+                buf.extend_from_slice(&data);
+                Ok(data.len())
+            }
+            Err(android_glue::AssetError::AssetMissing) => Err(AssetStoreError::NoSuchAsset),
+            Err(android_glue::AssetError::EmptyBuffer) => Err(AssetStoreError::NotAvailable),
+        }
+    }
+}
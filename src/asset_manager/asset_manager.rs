@@ -3,20 +3,32 @@
 
 use cgmath::{InnerSpace, Vector3};
 use dds::DDS;
-use fnv::FnvHashMap as HashMap;
+use fnv::{FnvHashMap as HashMap, FnvHashSet as HashSet};
 use gfx::texture::{AaMode, Kind};
 use imagefmt::{ColFmt, Image, read_from};
 use std::any::{Any, TypeId};
+use std::collections::HashMap as StdHashMap;
+use std::collections::hash_map;
 use std::{env, fs};
-use std::io::{Cursor, Read};
+use std::io::{self, Cursor, Read};
 use std::ops::{Deref, DerefMut};
-use std::path::{Path, PathBuf};
+use std::path::{Component as PathComponent, Path, PathBuf};
 use std::str;
 use std::sync::RwLockReadGuard;
-use wavefront_obj::obj::{ObjSet, parse, Primitive};
-
-use ecs::{Allocator, Component, Entity, MaskedStorage, Storage, VecStorage, World};
-use ecs::components::{Mesh, Renderable, Texture, TextureLoadData};
+use std::time::Instant;
+use wavefront_obj::{mtl, obj};
+use wavefront_obj::mtl::MtlSet;
+use wavefront_obj::obj::{ObjSet, Primitive};
+
+use asset_manager::{AssetError, AssetErrorKind, AssetInfo, AssetStats, AssetStoreError, LoaderError,
+                    Prefab, PreloadList, ProgressCounter};
+use config::Element;
+use ecs::{Allocator, Component, Entity, Join, MaskedStorage, Storage, VecStorage, World};
+use ecs::components::{AudioSource, Font, Mesh, Renderable, ShaderSource, SpriteSheet, Texture,
+                      TextureLoadData};
+#[cfg(feature="tiled")]
+use ecs::components::TileMap;
+use ecs::resources::Locale;
 use renderer::VertexPosNormal;
 
 type AssetTypeId = TypeId;
@@ -26,6 +38,22 @@ type LoaderTypeId = TypeId;
 /// An ID used for directly accessing assets in the manager.
 pub type AssetId = Entity;
 
+/// The load state of an asset, see `AssetManager::load_state`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoadState<'a> {
+    /// The asset hasn't been loaded, or has been unloaded.
+    NotLoaded,
+    /// The asset has been loaded successfully.
+    Loaded(AssetId),
+    /// The last attempt to load the asset failed with the given error
+    /// message.
+    Failed(&'a str),
+}
+
+/// A material loaded from a Wavefront `.mtl` file, see
+/// `AssetManager::load_mtl_material`.
+pub type ObjMaterial = mtl::Material;
+
 /// Generic wrapper around actual asset data.
 pub struct Asset<T>(pub T);
 
@@ -35,25 +63,46 @@ impl<T: Any + Send + Sync> Component for Asset<T> {
 
 /// Describes a raw asset loader type.
 pub trait AssetLoaderRaw: Sized {
-    /// TODO: Return a `Result` instead of an `Option`.
-    fn from_raw(assets: &Assets, data: &[u8]) -> Option<Self>;
+    /// Decodes the raw bytes of an asset.
+    fn from_raw(assets: &Assets, data: &[u8]) -> Result<Self, LoaderError>;
+
+    /// Narrows the decoded data down to the sub-asset `fragment`, for asset
+    /// names like `"models/scene#Cube"` addressing a part of a file.
+    ///
+    /// Returns an error by default, as most formats contain a single asset.
+    fn sub_asset(self, fragment: &str) -> Result<Self, LoaderError> {
+        Err(format!("No sub-asset \"{}\" in this format", fragment).into())
+    }
 }
 
 /// Describes an abstract asset loader type.
 pub trait AssetLoader<A> {
-    /// TODO: Return a `Result` instead of an `Option`.
-    fn from_data(assets: &mut Assets, data: Self) -> Option<A>;
+    /// Turns the decoded data into the final asset.
+    fn from_data(assets: &mut Assets, data: Self) -> Result<A, LoaderError>;
 }
 
 /// Describes a permanent storage for assets.
 pub trait AssetStore {
+    /// Returns a short description of the store (e.g. its directory), used in
+    /// error messages.
+    fn name(&self) -> String {
+        "asset store".into()
+    }
     /// Returns whether the asset store contains an asset with the given name
     /// and type strings.
     fn has_asset(&self, name: &str, asset_type: &str) -> bool;
     /// Loads an asset file with the given name and type into buffer `buf`.
     ///
-    /// Returns the final size of the asset in bytes, returns `None` on failure.
-    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Option<usize>;
+    /// Returns the final size of the asset in bytes.
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError>;
+    /// Returns the sorted file names (`name.type`) of all assets whose names
+    /// start with `prefix`, e.g. `"maps/"` for all maps.
+    ///
+    /// Returns `AssetStoreError::NotAvailable` if the store can't enumerate
+    /// its assets, which is the default.
+    fn list(&self, _prefix: &str) -> Result<Vec<String>, AssetStoreError> {
+        Err(AssetStoreError::NotAvailable)
+    }
 }
 
 /// Describes an asset reader type that can read assets of type `T`.
@@ -71,11 +120,26 @@ impl<'a, T: Any + Send + Sync> AssetReadStorage<T> for Storage<Asset<T>, RwLockR
     }
 }
 
+/// An iterator over the names and IDs of all loaded assets.
+pub struct AssetNames<'a> {
+    iterator: hash_map::Iter<'a, String, AssetId>,
+}
+
+impl<'a> Iterator for AssetNames<'a> {
+    type Item = (&'a str, AssetId);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.next().map(|(name, id)| (name.as_str(), *id))
+    }
+}
+
 /// Internal assets handler which takes care of storing and loading assets.
 pub struct Assets {
     loaders: HashMap<LoaderTypeId, Box<Any>>,
     asset_ids: HashMap<String, AssetId>,
+    aliases: HashMap<String, String>,
     assets: World,
+    defaults: HashMap<AssetTypeId, AssetId>,
+    registered: HashSet<AssetTypeId>,
 }
 
 impl Assets {
@@ -83,7 +147,10 @@ impl Assets {
         Assets {
             loaders: HashMap::default(),
             asset_ids: HashMap::default(),
+            aliases: HashMap::default(),
             assets: World::new(),
+            defaults: HashMap::default(),
+            registered: HashSet::default(),
         }
     }
 
@@ -110,11 +177,79 @@ impl Assets {
     /// Register a new asset type
     pub fn register_asset<A: Any + Send + Sync>(&mut self) {
         self.assets.register::<Asset<A>>();
+        self.registered.insert(TypeId::of::<A>());
     }
 
-    /// Retrieve the `AssetId` from the asset name
+    /// Retrieve the `AssetId` from the asset name or an alias of it
     pub fn id_from_name(&self, name: &str) -> Option<AssetId> {
-        self.asset_ids.get(name).map(|id| *id)
+        self.asset_ids.get(self.resolve_alias(name)).map(|id| *id)
+    }
+
+    /// Register `alias` as another name for the asset `name`, e.g.
+    /// `"player_skin"` for `"skins/default/body"`, replacing any previous
+    /// target of the alias. Aliases are resolved when loading and looking up
+    /// assets, but not recursively.
+    pub fn add_alias(&mut self, alias: &str, name: &str) {
+        self.aliases.insert(alias.into(), name.into());
+    }
+
+    /// Returns the asset name `name` is an alias for, or `name` itself if it
+    /// isn't an alias
+    pub fn resolve_alias<'a>(&'a self, name: &'a str) -> &'a str {
+        self.aliases.get(name).map(|name| name.as_str()).unwrap_or(name)
+    }
+
+    /// Set the asset which is used in place of missing assets of type `A`.
+    /// The default asset has to be loaded like any other asset first.
+    pub fn set_default<A: Any + Send + Sync>(&mut self, id: AssetId) {
+        self.defaults.insert(TypeId::of::<A>(), id);
+    }
+
+    /// Returns the `AssetId` of the default asset of type `A`, if one was set
+    pub fn default_id<A: Any + Send + Sync>(&self) -> Option<AssetId> {
+        self.defaults.get(&TypeId::of::<A>()).map(|id| *id)
+    }
+
+    /// Retrieve the `AssetId` of the asset of type `A` with the given name,
+    /// falling back to the default asset of type `A` if there is no such asset
+    pub fn id_from_name_or_default<A: Any + Send + Sync>(&self, name: &str) -> Option<AssetId> {
+        self.id_from_name(name)
+            .and_then(|id| if self.read_assets::<A>().get(id).is_some() {
+                Some(id)
+            } else {
+                None
+            })
+            .or_else(|| self.default_id::<A>())
+    }
+
+    /// Returns an iterator over the names and IDs of all loaded assets
+    pub fn asset_names(&self) -> AssetNames {
+        AssetNames { iterator: self.asset_ids.iter() }
+    }
+
+    /// Calls `f` with the ID and data of every loaded asset of type `A`
+    pub fn for_each_asset<A, F>(&self, mut f: F)
+        where A: Any + Send + Sync,
+              F: FnMut(AssetId, &A)
+    {
+        let entities = self.assets.entities();
+        let storage = self.read_assets::<A>();
+        for (id, asset) in (&entities, &storage).iter() {
+            f(id, &asset.0);
+        }
+    }
+
+    /// Calls `f` with the ID and mutable data of every loaded asset of type
+    /// `A`
+    pub fn for_each_asset_mut<A, F>(&mut self, mut f: F)
+        where A: Any + Send + Sync,
+              F: FnMut(AssetId, &mut A)
+    {
+        let entities = self.assets.entities();
+        let mut storage = self.assets.write::<Asset<A>>();
+        for (id, asset) in (&entities, &mut storage).iter() {
+            f(id, &mut asset.0);
+        }
     }
 
     /// Read the storage of all assets for a certain type
@@ -128,21 +263,55 @@ impl Assets {
     pub fn load_asset_from_data<A: Any + Sync + Send, S>(&mut self,
                                                          name: &str,
                                                          data: S)
-                                                         -> Option<AssetId>
+                                                         -> Result<AssetId, AssetError>
         where S: AssetLoader<A>
     {
-        let asset = AssetLoader::<A>::from_data(self, data);
-        if let Some(asset) = asset {
-            Some(self.add_asset(name, asset))
-        } else {
-            None
+        match AssetLoader::<A>::from_data(self, data) {
+            Ok(asset) => Ok(self.add_asset(name, asset)),
+            Err(e) => Err(AssetError::new(name, None, AssetErrorKind::Load(e))),
+        }
+    }
+
+    /// Add an asset generated at runtime, like a procedural mesh or a baked
+    /// navmesh, without a name. Use `load_asset_from_data` for assets which
+    /// should be looked up or unloaded by name.
+    ///
+    /// Returns `AssetErrorKind::UnregisteredAssetType` if `A` hasn't been
+    /// registered with `register_asset`.
+    pub fn insert_asset<A: Any + Send + Sync>(&mut self, asset: A) -> Result<AssetId, AssetError> {
+        if !self.registered.contains(&TypeId::of::<A>()) {
+            return Err(AssetError::new("", None, AssetErrorKind::UnregisteredAssetType));
         }
+
+        Ok(self.assets.create_now().with(Asset::<A>(asset)).build())
+    }
+
+    /// Unload the asset with the given name immediately, e.g. on level
+    /// transitions. Its `AssetId` becomes invalid.
+    ///
+    /// Returns `false` if no asset with that name has been loaded.
+    pub fn unload_asset(&mut self, name: &str) -> bool {
+        let name = self.resolve_alias(name).to_string();
+        let id = match self.asset_ids.remove(&name) {
+            Some(id) => id,
+            None => return false,
+        };
+
+        self.defaults.retain(|_, default| *default != id);
+        self.assets.delete_later(id);
+        self.assets.maintain();
+
+        true
     }
 
     fn add_asset<A: Any + Send + Sync>(&mut self, name: &str, asset: A) -> AssetId {
-        *self.asset_ids
-            .entry(name.into())
-            .or_insert(self.assets.create_now().with(Asset::<A>(asset)).build())
+        if let Some(&id) = self.asset_ids.get(name) {
+            return id;
+        }
+
+        let id = self.assets.create_now().with(Asset::<A>(asset)).build();
+        self.asset_ids.insert(name.into(), id);
+        id
     }
 }
 
@@ -151,8 +320,12 @@ pub struct AssetManager {
     assets: Assets,
     asset_type_ids: HashMap<(String, AssetTypeId), SourceTypeId>,
     closures: HashMap<(AssetTypeId, SourceTypeId),
-                      Box<FnMut(&mut Assets, &str, &[u8]) -> Option<AssetId>>>,
+                      Box<FnMut(&mut Assets, &str, &[u8]) -> Result<AssetId, AssetErrorKind>>>,
     stores: Vec<Box<AssetStore>>,
+    named_stores: HashMap<String, Box<AssetStore>>,
+    stats: AssetStats,
+    failures: HashMap<String, String>,
+    infos: HashMap<String, AssetInfo>,
 }
 
 impl AssetManager {
@@ -163,13 +336,25 @@ impl AssetManager {
             assets: Assets::new(),
             closures: HashMap::default(),
             stores: Vec::new(),
+            named_stores: HashMap::default(),
+            stats: AssetStats::new(),
+            failures: HashMap::default(),
+            infos: HashMap::default(),
         };
 
         // Handle some common use cases by default
         asset_manager.register_asset::<Mesh>();
         asset_manager.register_asset::<Texture>();
+        asset_manager.register_asset::<ObjMaterial>();
+        asset_manager.register_asset::<AudioSource>();
+        asset_manager.register_asset::<Font>();
+        asset_manager.register_asset::<SpriteSheet>();
+        asset_manager.register_asset::<ShaderSource>();
+        asset_manager.register_asset::<Locale>();
+        asset_manager.register_asset::<Prefab>();
 
         asset_manager.register_loader::<Mesh, ObjSet>("obj");
+        asset_manager.register_loader::<ObjMaterial, MtlSet>("mtl");
 
         for fmt in vec!["png", "bmp", "jpg", "jpeg", "tga"] {
             asset_manager.register_loader::<Texture, Image<u8>>(fmt);
@@ -177,6 +362,24 @@ impl AssetManager {
 
         asset_manager.register_loader::<Texture, DDS>("dds");
 
+        for fmt in vec!["wav", "ogg", "flac"] {
+            asset_manager.register_loader::<AudioSource, AudioSource>(fmt);
+        }
+
+        asset_manager.register_loader::<Font, Font>("ttf");
+        asset_manager.register_loader::<Font, Font>("otf");
+        asset_manager.register_loader::<Locale, Locale>("ftl");
+        asset_manager.register_loader::<Prefab, Prefab>("prefab");
+
+        #[cfg(feature="serde_json")]
+        asset_manager.register_loader::<SpriteSheet, SpriteSheet>("json");
+
+        #[cfg(feature="tiled")]
+        {
+            asset_manager.register_asset::<TileMap>();
+            asset_manager.register_loader::<TileMap, TileMap>("tmx");
+        }
+
         // Set up default resource directories. Will add each dir in
         // `AMETHYST_ASSET_DIRS` if set. Will also add the current
         // executable's sibling `./resources/assets/` directory.
@@ -203,12 +406,15 @@ impl AssetManager {
         let asset_id = TypeId::of::<A>();
         let source_id = TypeId::of::<S>();
 
-        self.closures.insert((asset_id, source_id),
-                             Box::new(|loader: &mut Assets, name: &str, raw: &[u8]| {
-                                 S::from_raw(loader, raw)
-                                     .and_then(|data| AssetLoader::<A>::from_data(loader, data))
-                                     .and_then(|asset| Some(loader.add_asset(name, asset)))
-                             }));
+        let closure = |loader: &mut Assets, name: &str, raw: &[u8]| -> Result<AssetId, AssetErrorKind> {
+            let mut data = S::from_raw(loader, raw).map_err(AssetErrorKind::Decode)?;
+            if let (_, Some(fragment)) = split_sub_asset(name) {
+                data = data.sub_asset(fragment).map_err(AssetErrorKind::Decode)?;
+            }
+            let asset = AssetLoader::<A>::from_data(loader, data).map_err(AssetErrorKind::Load)?;
+            Ok(loader.add_asset(name, asset))
+        };
+        self.closures.insert((asset_id, source_id), Box::new(closure));
 
         self.asset_type_ids.insert((asset.into(), asset_id), source_id);
     }
@@ -218,38 +424,660 @@ impl AssetManager {
         self.stores.push(Box::new(store));
     }
 
+    /// Register an asset store under the given key (e.g. `"core"` or
+    /// `"dlc1"`), replacing any store previously registered under it.
+    ///
+    /// Named stores are only used by `load_asset_from`, they are not searched
+    /// by `load_asset`.
+    pub fn register_named_store<T: 'static + AssetStore>(&mut self, key: &str, store: T) {
+        self.named_stores.insert(key.into(), Box::new(store));
+    }
+
     /// Load an asset from raw data
-    /// # Panics
-    /// Panics if the asset type isn't registered
     pub fn load_asset_from_raw<A: Any + Send + Sync>(&mut self,
                                                      name: &str,
                                                      asset_type: &str,
                                                      raw: &[u8])
-                                                     -> Option<AssetId> {
+                                                     -> Result<AssetId, AssetError> {
         let asset_type_id = TypeId::of::<A>();
-        let &source_id = self.asset_type_ids
-            .get(&(asset_type.into(), asset_type_id))
-            .expect("Unregistered asset type id");
-        let ref mut loader = self.closures.get_mut(&(asset_type_id, source_id)).unwrap();
-        loader(&mut self.assets, name, raw)
+        let source_id = match self.asset_type_ids.get(&(asset_type.into(), asset_type_id)) {
+            Some(&id) => id,
+            None => {
+                let e = AssetError::new(name, Some(asset_type), AssetErrorKind::UnregisteredAssetType);
+                self.record_failure(&e);
+                return Err(e);
+            }
+        };
+
+        self.decode(asset_type_id, source_id, name, asset_type, raw)
+    }
+
+    /// Decodes an asset with the loader registered for the given asset and
+    /// source types
+    fn decode(&mut self,
+              asset_type_id: AssetTypeId,
+              source_id: SourceTypeId,
+              name: &str,
+              asset_type: &str,
+              raw: &[u8])
+              -> Result<AssetId, AssetError> {
+        let start = Instant::now();
+        let result = {
+            let ref mut loader = self.closures.get_mut(&(asset_type_id, source_id)).unwrap();
+            loader(&mut self.assets, name, raw)
+        };
+        match result {
+            Ok(id) => {
+                let load_time = start.elapsed();
+                self.stats.record_load(asset_type, raw.len(), load_time);
+                self.failures.remove(name);
+                self.infos.insert(name.into(),
+                                  AssetInfo {
+                                      asset_type: asset_type.into(),
+                                      store: None,
+                                      num_bytes: raw.len(),
+                                      load_time: load_time,
+                                      dependencies: Vec::new(),
+                                  });
+                Ok(id)
+            }
+            Err(kind) => {
+                let e = AssetError::new(name, Some(asset_type), kind);
+                self.record_failure(&e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns the load state of the asset with the given name.
+    pub fn load_state(&self, name: &str) -> LoadState {
+        if let Some(id) = self.id_from_name(name) {
+            return LoadState::Loaded(id);
+        }
+
+        match self.failures.get(self.resolve_alias(name)) {
+            Some(error) => LoadState::Failed(error),
+            None => LoadState::NotLoaded,
+        }
+    }
+
+    /// Records a failed load in the statistics and load states
+    fn record_failure(&mut self, error: &AssetError) {
+        if let Some(ref asset_type) = error.asset_type {
+            self.stats.record_failure(asset_type);
+        }
+        self.failures.insert(error.name.clone(), error.to_string());
+    }
+
+    /// Returns information about the loaded asset with the given name, like
+    /// the store it was read from and the assets it depends on. Assets added
+    /// with `load_asset_from_data` have no information.
+    pub fn asset_info(&self, name: &str) -> Option<&AssetInfo> {
+        if self.id_from_name(name).is_none() {
+            return None;
+        }
+        self.infos.get(self.resolve_alias(name))
+    }
+
+    /// Unload the asset with the given name immediately, e.g. on level
+    /// transitions, and forget its information. Its `AssetId` becomes
+    /// invalid.
+    ///
+    /// Returns `false` if no asset with that name has been loaded.
+    pub fn unload_asset(&mut self, name: &str) -> bool {
+        let name = self.resolve_alias(name).to_string();
+        if !self.assets.unload_asset(&name) {
+            return false;
+        }
+
+        self.infos.remove(&name);
+        true
+    }
+
+    /// Returns the names of all loaded assets depending on the asset with the
+    /// given name, e.g. to find out why a texture is still loaded.
+    pub fn dependents(&self, name: &str) -> Vec<&str> {
+        let name = self.resolve_alias(name);
+        let mut dependents = self.infos
+            .iter()
+            .filter(|&(dependent, info)| {
+                info.dependencies.iter().any(|dependency| dependency == name) &&
+                self.id_from_name(dependent).is_some()
+            })
+            .map(|(dependent, _)| dependent.as_str())
+            .collect::<Vec<_>>();
+        dependents.sort();
+        dependents
+    }
+
+    /// Records the store a loaded asset was read from
+    fn set_store(&mut self, name: &str, store: String) {
+        if let Some(info) = self.infos.get_mut(name) {
+            info.store = Some(store);
+        }
+    }
+
+    /// Records an asset loaded along with another asset
+    fn add_dependency(&mut self, name: &str, dependency: &str) {
+        if let Some(info) = self.infos.get_mut(name) {
+            if !info.dependencies.iter().any(|existing| existing == dependency) {
+                info.dependencies.push(dependency.into());
+            }
+        }
     }
 
     /// Load an asset from the asset stores
+    ///
+    /// A name like `"models/scene#Cube"` loads the sub-asset `Cube` of the
+    /// file `models/scene`, see `AssetLoaderRaw::sub_asset`. If an asset of
+    /// type `A` with that name has been loaded before, its id is returned
+    /// without reading the asset again.
     pub fn load_asset<A: Any + Send + Sync>(&mut self,
                                             name: &str,
                                             asset_type: &str)
-                                            -> Option<AssetId> {
-        let mut buf = Vec::new();
-        if let Some(store) = self.stores.iter().find(|store| store.has_asset(name, asset_type)) {
-            store.load_asset(name, asset_type, &mut buf);
+                                            -> Result<AssetId, AssetError> {
+        let name = &self.resolve_alias(name).to_string();
+        if let Some(id) = self.loaded_id::<A>(name) {
+            return Ok(id);
+        }
+
+        let (buf, store) = match self.read_from_stores(name, asset_type) {
+            Ok(read) => read,
+            Err(e) => {
+                self.record_failure(&e);
+                return Err(e);
+            }
+        };
+        let id = self.load_asset_from_raw::<A>(name, asset_type, &buf)?;
+        self.set_store(name, store);
+        Ok(id)
+    }
+
+    /// Returns statistics about the assets loaded so far. Use
+    /// `stats_mut().clear()` to start measuring from scratch.
+    pub fn stats(&self) -> &AssetStats {
+        &self.stats
+    }
+
+    /// Returns mutable statistics about the assets loaded so far.
+    pub fn stats_mut(&mut self) -> &mut AssetStats {
+        &mut self.stats
+    }
+
+    /// Returns the id of the asset with the given name if it has already been
+    /// loaded as an `A`
+    fn loaded_id<A: Any + Send + Sync>(&self, name: &str) -> Option<AssetId> {
+        self.id_from_name(name).and_then(|id| if self.read_assets::<A>().read(id).is_some() {
+            Some(id)
         } else {
-            return None;
+            None
+        })
+    }
+
+    /// Reads the raw data of an asset from the first asset store containing
+    /// it, and returns it along with the name of the store
+    fn read_from_stores(&self, name: &str, asset_type: &str) -> Result<(Vec<u8>, String), AssetError> {
+        let (file, _) = split_sub_asset(name);
+        let mut buf = Vec::new();
+        match self.stores.iter().find(|store| store.has_asset(file, asset_type)) {
+            Some(store) => {
+                read_from_store(&**store, name, asset_type, &mut buf)?;
+                Ok((buf, store.name()))
+            }
+            None => Err(AssetError::new(name, Some(asset_type), AssetErrorKind::NoSuchAsset)),
+        }
+    }
+
+    /// Load an asset from the asset stores given its path, e.g.
+    /// `"textures/hero.png"`. The loader is picked by the file extension, so
+    /// `load_asset_by_path::<Texture>("textures/hero.png")` is equivalent to
+    /// `load_asset::<Texture>("textures/hero", "png")`. Sub-assets are
+    /// addressed as in `"models/scene.obj#Cube"`.
+    pub fn load_asset_by_path<A: Any + Send + Sync>(&mut self, path: &str) -> Result<AssetId, AssetError> {
+        let (file, fragment) = split_sub_asset(path);
+        match split_extension(file) {
+            Some((name, asset_type)) => {
+                match fragment {
+                    Some(fragment) => self.load_asset::<A>(&format!("{}#{}", name, fragment), asset_type),
+                    None => self.load_asset::<A>(name, asset_type),
+                }
+            }
+            None => Err(AssetError::new(path, None, AssetErrorKind::UnregisteredAssetType)),
+        }
+    }
+
+    /// Load an asset from the asset store registered under the given key.
+    /// Assets which have already been loaded are returned as in
+    /// `load_asset`.
+    pub fn load_asset_from<A: Any + Send + Sync>(&mut self,
+                                                 key: &str,
+                                                 name: &str,
+                                                 asset_type: &str)
+                                                 -> Result<AssetId, AssetError> {
+        let name = &self.resolve_alias(name).to_string();
+        if let Some(id) = self.loaded_id::<A>(name) {
+            return Ok(id);
+        }
+
+        let mut buf = Vec::new();
+        let read = match self.named_stores.get(key) {
+            Some(store) => read_from_store(&**store, name, asset_type, &mut buf).map(|_| store.name()),
+            None => {
+                let kind = AssetErrorKind::UnregisteredStore(key.into());
+                Err(AssetError::new(name, Some(asset_type), kind))
+            }
+        };
+        let store = match read {
+            Ok(store) => store,
+            Err(e) => {
+                self.record_failure(&e);
+                return Err(e);
+            }
+        };
+
+        let id = self.load_asset_from_raw::<A>(name, asset_type, &buf)?;
+        self.set_store(name, store);
+        Ok(id)
+    }
+
+    /// Load an asset from the asset stores and record the outcome in the
+    /// given `ProgressCounter`
+    pub fn load_asset_with_progress<A: Any + Send + Sync>(&mut self,
+                                                          name: &str,
+                                                          asset_type: &str,
+                                                          progress: &mut ProgressCounter)
+                                                          -> Result<AssetId, AssetError> {
+        let id = self.load_asset::<A>(name, asset_type);
+        progress.track(id.is_ok());
+        id
+    }
+
+    /// Load a batch of assets, given as `(name, asset_type)` pairs, from the
+    /// asset stores and record their outcomes in the given `ProgressCounter`.
+    ///
+    /// The raw data of all assets is read before any of them is decoded, so
+    /// stores get a chance to serve the reads back to back. Assets which have
+    /// already been loaded, or which appear twice in the batch, are only read
+    /// once. The results are returned in the order of `assets`.
+    pub fn load_assets<A: Any + Send + Sync>(&mut self,
+                                             assets: &[(&str, &str)],
+                                             progress: &mut ProgressCounter)
+                                             -> Vec<Result<AssetId, AssetError>> {
+        let names = assets.iter()
+            .map(|&(name, _)| self.resolve_alias(name).to_string())
+            .collect::<Vec<_>>();
+        let raw = assets.iter()
+            .enumerate()
+            .map(|(index, &(_, asset_type))| {
+                let name = &names[index];
+                let duplicate = names[..index].contains(name);
+                if duplicate || self.loaded_id::<A>(name).is_some() {
+                    None
+                } else {
+                    Some(self.read_from_stores(name, asset_type))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        assets.iter()
+            .zip(names.iter())
+            .zip(raw)
+            .map(|((&(_, asset_type), name), buf)| {
+                let id = match buf {
+                    Some(Ok((buf, store))) => {
+                        let id = self.load_asset_from_raw::<A>(name, asset_type, &buf);
+                        if id.is_ok() {
+                            self.set_store(name, store);
+                        }
+                        id
+                    }
+                    Some(Err(e)) => {
+                        self.record_failure(&e);
+                        Err(e)
+                    }
+                    // Loaded before or earlier in the batch.
+                    None => self.load_asset::<A>(name, asset_type),
+                };
+                progress.track(id.is_ok());
+                id
+            })
+            .collect()
+    }
+
+    /// Load all assets of a YAML `.preload` file (see `PreloadList`) and
+    /// record their outcomes in the given `ProgressCounter`. Typically called
+    /// from `State::on_start`.
+    ///
+    /// Every asset type string of the list has to be registered for a single
+    /// kind of asset, like `"png"` for `Texture`s. Assets which fail to load
+    /// are counted as failed in `progress`, and their errors are available
+    /// through `load_state`. An error is only returned if the list itself
+    /// can't be loaded.
+    pub fn preload(&mut self, name: &str, progress: &mut ProgressCounter) -> Result<(), AssetError> {
+        let (buf, _) = self.read_from_stores(name, "preload")?;
+        let source = str::from_utf8(&buf)
+            .map_err(|e| AssetError::new(name, Some("preload"), AssetErrorKind::Decode(e.into())))?;
+        let list = PreloadList::from_string(source).map_err(|e| {
+                let kind = AssetErrorKind::Decode(e.to_string().into());
+                AssetError::new(name, Some("preload"), kind)
+            })?;
+
+        for &(ref name, ref asset_type) in &list.assets {
+            let id = self.load_asset_by_type(name, asset_type);
+            progress.track(id.is_ok());
+        }
+
+        Ok(())
+    }
+
+    /// Load an asset from the asset stores using the only loader registered
+    /// for its asset type string
+    fn load_asset_by_type(&mut self, name: &str, asset_type: &str) -> Result<AssetId, AssetError> {
+        let name = &self.resolve_alias(name).to_string();
+        if let Some(id) = self.id_from_name(name) {
+            return Ok(id);
+        }
+
+        let key = {
+            let mut loaders = self.asset_type_ids
+                .iter()
+                .filter(|&(&(ref ty, _), _)| ty == asset_type)
+                .map(|(&(_, asset_type_id), &source_id)| (asset_type_id, source_id));
+            match (loaders.next(), loaders.next()) {
+                (Some(key), None) => Some(key),
+                _ => None,
+            }
+        };
+        let (asset_type_id, source_id) = match key {
+            Some(key) => key,
+            None => {
+                let e = AssetError::new(name, Some(asset_type), AssetErrorKind::UnregisteredAssetType);
+                self.record_failure(&e);
+                return Err(e);
+            }
+        };
+
+        let (buf, store) = match self.read_from_stores(name, asset_type) {
+            Ok(read) => read,
+            Err(e) => {
+                self.record_failure(&e);
+                return Err(e);
+            }
+        };
+        let id = self.decode(asset_type_id, source_id, name, asset_type, &buf)?;
+        self.set_store(name, store);
+        Ok(id)
+    }
+
+    /// Load a table of aliases from a YAML `.aliases` file mapping aliases to
+    /// asset names, and register them with `add_alias`. Returns the number
+    /// of aliases.
+    ///
+    /// ```yaml
+    /// player_skin: "skins/default/body"
+    /// main_theme: "music/title"
+    /// ```
+    pub fn load_aliases(&mut self, name: &str) -> Result<usize, AssetError> {
+        let (buf, _) = self.read_from_stores(name, "aliases")?;
+        let source = str::from_utf8(&buf)
+            .map_err(|e| AssetError::new(name, Some("aliases"), AssetErrorKind::Decode(e.into())))?;
+        let aliases = StdHashMap::<String, String>::from_string(source).map_err(|e| {
+                let kind = AssetErrorKind::Decode(e.to_string().into());
+                AssetError::new(name, Some("aliases"), kind)
+            })?;
+
+        let num_aliases = aliases.len();
+        for (alias, target) in aliases {
+            self.add_alias(&alias, &target);
+        }
+
+        Ok(num_aliases)
+    }
+
+    /// Load a material from a Wavefront `.mtl` file, given as sub-asset of the
+    /// file like `"materials/cube#Red"`, and create constant color textures
+    /// from its ambient, diffuse, and specular colors.
+    ///
+    /// The textures are named `"<material>.ka"`, `"<material>.kd"`, and
+    /// `"<material>.ks"`, so they can be passed to `create_renderable` along
+    /// with the material's `specular_coefficient`. Use `load_obj_materials`
+    /// to load all materials used by an OBJ file. Texture maps aren't
+    /// supported yet.
+    pub fn load_mtl_material(&mut self, material: &str) -> Result<AssetId, AssetError> {
+        let id = self.load_asset::<ObjMaterial>(material, "mtl")?;
+        let colors = {
+            let materials = self.read_assets::<ObjMaterial>();
+            let loaded = match materials.read(id) {
+                Some(loaded) => loaded,
+                None => return Err(AssetError::new(material, Some("mtl"), AssetErrorKind::NameTaken)),
+            };
+            let alpha = loaded.alpha as f32;
+            [("ka", color_to_rgba(loaded.color_ambient, alpha)),
+             ("kd", color_to_rgba(loaded.color_diffuse, alpha)),
+             ("ks", color_to_rgba(loaded.color_specular, alpha))]
+        };
+
+        for &(channel, color) in &colors {
+            let name = format!("{}.{}", material, channel);
+            self.load_asset_from_data::<Texture, [f32; 4]>(&name, color)?;
+        }
+
+        Ok(id)
+    }
+
+    /// Load the materials used by a Wavefront OBJ file from the material
+    /// library it names with `mtllib`, using `load_mtl_material`. The library
+    /// path is relative to the OBJ file, and the materials are named like
+    /// `"models/cube#Red"` after the library and the material.
+    ///
+    /// Returns the IDs of the materials, which is empty if the OBJ file
+    /// doesn't name a material library.
+    pub fn load_obj_materials(&mut self, name: &str) -> Result<Vec<AssetId>, AssetError> {
+        let (buf, _) = self.read_from_stores(name, "obj")?;
+        let mut obj_set = ObjSet::from_raw(&self.assets, &buf)
+            .map_err(|e| AssetError::new(name, Some("obj"), AssetErrorKind::Decode(e)))?;
+        let (file, fragment) = split_sub_asset(name);
+        if let Some(fragment) = fragment {
+            obj_set = obj_set.sub_asset(fragment)
+                .map_err(|e| AssetError::new(name, Some("obj"), AssetErrorKind::Decode(e)))?;
+        }
+
+        let library = match obj_set.material_library {
+            Some(ref library) => path_to_name(&relative_path(file, library)).to_string(),
+            None => return Ok(Vec::new()),
+        };
+
+        let mut materials = Vec::new();
+        for object in &obj_set.objects {
+            for geometry in &object.geometry {
+                if let Some(ref material) = geometry.material_name {
+                    if !materials.contains(material) {
+                        materials.push(material.clone());
+                    }
+                }
+            }
+        }
+
+        let mut ids = Vec::new();
+        for material in materials {
+            ids.push(self.load_mtl_material(&format!("{}#{}", library, material))?);
+        }
+        self.add_dependency(file, &library);
+
+        Ok(ids)
+    }
+
+    /// Load a sprite sheet from the asset stores, along with the texture it
+    /// references.
+    ///
+    /// The texture path is relative to the sprite sheet, and the texture is
+    /// named after its path without extension like any other asset loaded by
+    /// `load_asset_by_path`.
+    pub fn load_sprite_sheet(&mut self, name: &str, asset_type: &str) -> Result<AssetId, AssetError> {
+        let id = self.load_asset::<SpriteSheet>(name, asset_type)?;
+        let texture = {
+            let sheets = self.read_assets::<SpriteSheet>();
+            match sheets.read(id) {
+                Some(sheet) => sheet.texture.clone(),
+                None => return Err(AssetError::new(name, Some(asset_type), AssetErrorKind::NameTaken)),
+            }
+        };
+
+        let path = relative_path(name, &texture);
+        self.load_asset_by_path::<Texture>(&path)?;
+        self.add_dependency(name, path_to_name(&path));
+
+        Ok(id)
+    }
+
+    /// Load a Tiled map from the asset stores, along with the images of its
+    /// tilesets. Requires the `tiled` feature.
+    ///
+    /// Image paths are relative to the map, and the textures are named after
+    /// their paths without extension like any other asset loaded by
+    /// `load_asset_by_path`.
+    #[cfg(feature="tiled")]
+    pub fn load_tile_map(&mut self, name: &str) -> Result<AssetId, AssetError> {
+        let id = self.load_asset::<TileMap>(name, "tmx")?;
+        let images = {
+            let maps = self.read_assets::<TileMap>();
+            let map = match maps.read(id) {
+                Some(map) => map,
+                None => return Err(AssetError::new(name, Some("tmx"), AssetErrorKind::NameTaken)),
+            };
+            map.tilesets
+                .iter()
+                .flat_map(|tileset| tileset.images.iter().map(|image| image.source.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        for image in images {
+            let path = relative_path(name, &image);
+            self.load_asset_by_path::<Texture>(&path)?;
+            self.add_dependency(name, path_to_name(&path));
+        }
+
+        Ok(id)
+    }
+
+    /// Load GLSL shader source code from the asset stores, expanding
+    /// `#include "common.glsl"` directives. Included paths are relative to
+    /// the including file and are read from the asset stores as well.
+    pub fn load_shader(&mut self, name: &str, asset_type: &str) -> Result<AssetId, AssetError> {
+        let mut includes = Vec::new();
+        let path = format!("{}.{}", name, asset_type);
+        let source = self.expand_includes(&path, &mut vec![], &mut includes)?;
+
+        let shader = ShaderSource {
+            source: source,
+            includes: includes,
+        };
+        self.load_asset_from_data::<ShaderSource, ShaderSource>(name, shader)
+    }
+
+    /// Reads the shader at `path` and recursively expands its includes.
+    /// `stack` holds the files currently being expanded, to detect cycles.
+    fn expand_includes(&self,
+                       path: &str,
+                       stack: &mut Vec<String>,
+                       includes: &mut Vec<String>)
+                       -> Result<String, AssetError> {
+        let (name, asset_type) = match split_extension(path) {
+            Some(split) => split,
+            None => return Err(AssetError::new(path, None, AssetErrorKind::NoSuchAsset)),
+        };
+        let (data, _) = self.read_from_stores(name, asset_type)?;
+        let decode_error = |e: LoaderError| AssetError::new(name, Some(asset_type), AssetErrorKind::Decode(e));
+        let text = str::from_utf8(&data).map_err(|e| decode_error(e.into()))?;
+
+        stack.push(path.into());
+        let mut source = String::with_capacity(text.len());
+        for line in text.lines() {
+            match parse_include(line) {
+                Some(include) => {
+                    let include = relative_path(path, include);
+                    if stack.contains(&include) {
+                        return Err(decode_error(format!("Recursive include of \"{}\"", include).into()));
+                    }
+                    if !includes.contains(&include) {
+                        includes.push(include.clone());
+                    }
+                    source.push_str(&self.expand_includes(&include, stack, includes)?);
+                }
+                None => {
+                    source.push_str(line);
+                    source.push('\n');
+                }
+            }
+        }
+        stack.pop();
+
+        Ok(source)
+    }
+
+    /// Load a `Prefab` from the asset stores, along with all prefabs nested
+    /// in it.
+    pub fn load_prefab(&mut self, name: &str) -> Result<AssetId, AssetError> {
+        self.load_prefab_nested(name, &mut vec![])
+    }
+
+    /// Loads a prefab and its nested prefabs. `stack` holds the prefabs
+    /// currently being loaded, to detect cycles.
+    fn load_prefab_nested(&mut self, name: &str, stack: &mut Vec<String>) -> Result<AssetId, AssetError> {
+        let id = self.load_asset::<Prefab>(name, "prefab")?;
+        let (nested, assets) = {
+            let prefabs = self.read_assets::<Prefab>();
+            let prefab = match prefabs.read(id) {
+                Some(prefab) => prefab,
+                None => return Err(AssetError::new(name, Some("prefab"), AssetErrorKind::NameTaken)),
+            };
+            let nested = prefab.entities
+                .iter()
+                .filter_map(|entity| entity.prefab.clone())
+                .collect::<Vec<_>>();
+            let assets = prefab.entities
+                .iter()
+                .filter_map(|entity| entity.renderable.as_ref())
+                .flat_map(|r| vec![r.mesh.clone(), r.ambient.clone(), r.diffuse.clone(), r.specular.clone()])
+                .filter(|name| !name.is_empty())
+                .collect::<Vec<_>>();
+            (nested, assets)
+        };
+        for dependency in nested.iter().chain(&assets) {
+            self.add_dependency(name, dependency);
+        }
+
+        stack.push(name.into());
+        for nested in nested {
+            if stack.contains(&nested) {
+                let kind = AssetErrorKind::Decode(format!("Prefab \"{}\" contains itself", nested).into());
+                return Err(AssetError::new(name, Some("prefab"), kind));
+            }
+            self.load_prefab_nested(&nested, stack)?;
         }
+        stack.pop();
 
-        self.load_asset_from_raw::<A>(name, asset_type, &buf)
+        Ok(id)
+    }
+
+    /// Spawn the entities of a loaded `Prefab` in `world`, returning the
+    /// root entity, see `Prefab::instantiate`.
+    pub fn instantiate_prefab(&self, name: &str, world: &mut World) -> Option<Entity> {
+        let id = match self.id_from_name(name) {
+            Some(id) => id,
+            None => return None,
+        };
+        let prefabs = self.read_assets::<Prefab>();
+        match prefabs.read(id) {
+            Some(prefab) => prefab.instantiate(world, self),
+            None => None,
+        }
     }
 
     /// Create a `Renderable` component from a loaded mesh and ka/kd/ks textures
+    ///
+    /// Falls back to the default mesh or texture for assets which haven't
+    /// been loaded, see `Assets::set_default`.
     pub fn create_renderable(&self,
                              mesh: &str,
                              ka: &str,
@@ -259,7 +1087,7 @@ impl AssetManager {
                              -> Option<Renderable> {
         let meshes = self.read_assets::<Mesh>();
         let textures = self.read_assets::<Texture>();
-        let mesh_id = match self.id_from_name(mesh) {
+        let mesh_id = match self.id_from_name_or_default::<Mesh>(mesh) {
             Some(id) => id,
             None => return None,
         };
@@ -267,7 +1095,7 @@ impl AssetManager {
             Some(mesh) => mesh,
             None => return None,
         };
-        let ka_id = match self.id_from_name(ka) {
+        let ka_id = match self.id_from_name_or_default::<Texture>(ka) {
             Some(id) => id,
             None => return None,
         };
@@ -275,7 +1103,7 @@ impl AssetManager {
             Some(ka) => ka,
             None => return None,
         };
-        let kd_id = match self.id_from_name(kd) {
+        let kd_id = match self.id_from_name_or_default::<Texture>(kd) {
             Some(id) => id,
             None => return None,
         };
@@ -283,7 +1111,7 @@ impl AssetManager {
             Some(kd) => kd,
             None => return None,
         };
-        let ks_id = match self.id_from_name(ks) {
+        let ks_id = match self.id_from_name_or_default::<Texture>(ks) {
             Some(id) => id,
             None => return None,
         };
@@ -315,70 +1143,311 @@ impl DerefMut for AssetManager {
     }
 }
 
-/// Asset store representing a file directory.
-pub struct DirectoryStore {
-    path: PathBuf,
+/// Normalizes an asset path for case-insensitive lookups: backslashes become
+/// slashes, empty and `.` components are dropped, and the path is lowercased.
+/// So `Textures\Hero.PNG` and `textures/./hero.png` both become
+/// `textures/hero.png`.
+pub fn normalize_path(path: &str) -> String {
+    path.split(|c: char| c == '/' || c == '\\')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect::<Vec<_>>()
+        .join("/")
+        .to_lowercase()
 }
 
-impl DirectoryStore {
-    /// Creates a new asset store from the given directory path.
-    pub fn new<P: AsRef<Path>>(path: P) -> DirectoryStore {
-        DirectoryStore { path: path.as_ref().to_path_buf() }
+/// Returns the name of the asset loaded by `load_asset_by_path` for `path`.
+fn path_to_name(path: &str) -> &str {
+    split_extension(path).map(|(name, _)| name).unwrap_or(path)
+}
+
+/// Splits a path into the asset name and the file extension used as asset
+/// type. Only the last path component is searched for the extension, and
+/// `None` is returned if it has none.
+pub fn split_extension(path: &str) -> Option<(&str, &str)> {
+    let file_start = path.rfind('/').map(|index| index + 1).unwrap_or(0);
+    match path[file_start..].rfind('.') {
+        Some(0) | None => None,
+        Some(index) => {
+            let index = file_start + index;
+            Some((&path[..index], &path[index + 1..]))
+        }
     }
+}
 
-    /// Returns the path to an asset file given the asset's name and type.
-    fn asset_to_path<'a>(&self, name: &str, asset_type: &str) -> PathBuf {
-        let file_name = format!("{}.{}", name, asset_type);
-        self.path.join(file_name)
+/// Resolves a path found in the asset `name` relative to the asset's
+/// directory. `.` and `..` components are collapsed, so that only paths
+/// leaving the asset root keep a leading `..`.
+fn relative_path(name: &str, path: &str) -> String {
+    let joined = match name.rfind('/') {
+        Some(index) => format!("{}{}", &name[..index + 1], path),
+        None => path.into(),
+    };
+
+    let mut parts: Vec<&str> = Vec::new();
+    for part in joined.split('/') {
+        match part {
+            "." => (),
+            ".." if parts.last().map_or(false, |last| *last != ".." && !last.is_empty()) => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
     }
+    parts.join("/")
 }
 
-impl AssetStore for DirectoryStore {
-    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
-        let file_path = self.asset_to_path(name, asset_type);
-        fs::metadata(file_path).ok().map(|meta| meta.is_file()).is_some()
+/// Returns the path of an `#include "path"` directive.
+fn parse_include(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if !line.starts_with("#include") {
+        return None;
     }
 
-    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Option<usize> {
-        let file_path = self.asset_to_path(name, asset_type);
-        let mut file = if let Ok(file) = fs::File::open(file_path) {
-            file
-        } else {
-            return None;
-        };
-        file.read_to_end(buf).ok()
+    let path = line["#include".len()..].trim();
+    if path.len() >= 2 && path.starts_with('"') && path.ends_with('"') {
+        Some(&path[1..path.len() - 1])
+    } else {
+        None
     }
 }
 
-impl AssetLoaderRaw for Image<u8> {
-    fn from_raw(_: &Assets, data: &[u8]) -> Option<Image<u8>> {
-        read_from(&mut Cursor::new(data), ColFmt::RGBA).ok()
+/// Splits an asset name like `"models/scene#Cube"` into the name of the file
+/// and the sub-asset.
+fn split_sub_asset(name: &str) -> (&str, Option<&str>) {
+    match name.find('#') {
+        Some(index) => (&name[..index], Some(&name[index + 1..])),
+        None => (name, None),
     }
 }
 
-impl AssetLoader<Texture> for Image<u8> {
-    fn from_data(assets: &mut Assets, image: Image<u8>) -> Option<Texture> {
-        let pixels = image.buf.chunks(4).map(|p| [p[0], p[1], p[2], p[3]]).collect::<Vec<_>>();
-
-        AssetLoader::from_data(assets,
-                               TextureLoadData {
-                                   kind: Kind::D2(image.w as u16, image.h as u16, AaMode::Single),
-                                   raw: &[pixels.as_slice()],
-                               })
+/// Reads an asset from the given store, attaching the store's name to errors.
+/// Sub-assets are read from the file containing them.
+fn read_from_store(store: &AssetStore,
+                   name: &str,
+                   asset_type: &str,
+                   buf: &mut Vec<u8>)
+                   -> Result<(), AssetError> {
+    let (file, _) = split_sub_asset(name);
+    match store.load_asset(file, asset_type, buf) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            let kind = AssetErrorKind::Store {
+                store: store.name(),
+                error: e,
+            };
+            Err(AssetError::new(name, Some(asset_type), kind))
+        }
     }
 }
 
-impl AssetLoaderRaw for DDS {
-    fn from_raw(_: &Assets, data: &[u8]) -> Option<DDS> {
-        DDS::decode(&mut data.clone())
-    }
+/// Asset store representing a file directory.
+///
+/// Asset names containing `..`, absolute paths, or drive prefixes are
+/// rejected with `AssetStoreError::PermissionDenied`, so names coming from
+/// untrusted sources like mods can't escape the directory.
+pub struct DirectoryStore {
+    path: PathBuf,
+    sandboxed: bool,
+    normalized: bool,
 }
 
-impl AssetLoader<Texture> for DDS {
-    fn from_data(assets: &mut Assets, image: DDS) -> Option<Texture> {
-        AssetLoader::from_data(assets,
-                               TextureLoadData {
-                                   kind: Kind::D2(image.header.width as u16,
+impl DirectoryStore {
+    /// Creates a new asset store from the given directory path.
+    pub fn new<P: AsRef<Path>>(path: P) -> DirectoryStore {
+        DirectoryStore {
+            path: path.as_ref().to_path_buf(),
+            sandboxed: false,
+            normalized: false,
+        }
+    }
+
+    /// Creates a new asset store from the given directory path which
+    /// additionally refuses to follow symbolic links out of the directory.
+    /// This resolves the path of every loaded asset, so it is slightly
+    /// slower than `new`.
+    pub fn sandboxed<P: AsRef<Path>>(path: P) -> DirectoryStore {
+        DirectoryStore {
+            path: path.as_ref().to_path_buf(),
+            sandboxed: true,
+            normalized: false,
+        }
+    }
+
+    /// Makes asset lookups ignore case and accept backslashes as separators,
+    /// so assets authored on case-insensitive file systems load everywhere.
+    /// See `normalize_path`.
+    pub fn with_normalized_paths(mut self) -> DirectoryStore {
+        self.normalized = true;
+        self
+    }
+
+    /// Returns the path to an asset file given the asset's name and type.
+    fn asset_to_path(&self, name: &str, asset_type: &str) -> Result<PathBuf, AssetStoreError> {
+        let mut file_name = format!("{}.{}", name, asset_type);
+        if self.normalized {
+            file_name = normalize_path(&file_name);
+        }
+        check_relative_path(&file_name)?;
+
+        let mut path = self.path.join(&file_name);
+        if self.normalized && !path.exists() {
+            if let Some(found) = find_ignoring_case(&self.path, &file_name) {
+                path = found;
+            }
+        }
+
+        if self.sandboxed {
+            let root = self.path.canonicalize()?;
+            if !path.canonicalize().map_err(missing_file)?.starts_with(root) {
+                return Err(AssetStoreError::PermissionDenied);
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+/// Looks up the normalized path `rel` in `root`, ignoring the case of every
+/// path component.
+fn find_ignoring_case(root: &Path, rel: &str) -> Option<PathBuf> {
+    let mut path = root.to_path_buf();
+    for part in rel.split('/') {
+        let exact = path.join(part);
+        if exact.exists() {
+            path = exact;
+            continue;
+        }
+
+        let entries = match fs::read_dir(&path) {
+            Ok(entries) => entries,
+            Err(_) => return None,
+        };
+        match entries.filter_map(|entry| entry.ok())
+            .find(|entry| entry.file_name().to_string_lossy().to_lowercase() == part) {
+            Some(entry) => path = entry.path(),
+            None => return None,
+        }
+    }
+
+    Some(path)
+}
+
+/// Reports a missing file as `NoSuchAsset`, so that e.g. `OverlayStore` falls
+/// through to the next store.
+fn missing_file(e: io::Error) -> AssetStoreError {
+    if e.kind() == io::ErrorKind::NotFound {
+        AssetStoreError::NoSuchAsset
+    } else {
+        AssetStoreError::Io(e)
+    }
+}
+
+/// Makes sure `path` is a relative path which doesn't leave the directory
+/// it's relative to, and returns `AssetStoreError::PermissionDenied`
+/// otherwise. Stores mapping asset names to files should check the names
+/// with this first.
+pub fn check_relative_path(path: &str) -> Result<(), AssetStoreError> {
+    // Drive prefixes are only parsed as such on Windows, so reject them
+    // explicitly to behave the same on every platform.
+    if path.contains(':') {
+        return Err(AssetStoreError::PermissionDenied);
+    }
+
+    for component in Path::new(path).components() {
+        match component {
+            PathComponent::Normal(_) | PathComponent::CurDir => (),
+            PathComponent::ParentDir | PathComponent::RootDir | PathComponent::Prefix(_) => {
+                return Err(AssetStoreError::PermissionDenied)
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl AssetStore for DirectoryStore {
+    fn name(&self) -> String {
+        format!("directory \"{}\"", self.path.display())
+    }
+
+    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
+        match self.asset_to_path(name, asset_type) {
+            Ok(file_path) => fs::metadata(file_path).ok().map(|meta| meta.is_file()).is_some(),
+            Err(_) => false,
+        }
+    }
+
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+        let file_path = self.asset_to_path(name, asset_type)?;
+        let mut file = fs::File::open(file_path).map_err(missing_file)?;
+        Ok(file.read_to_end(buf)?)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, AssetStoreError> {
+        // Only walk the directory the prefix points into.
+        let dir = match prefix.rfind('/') {
+            Some(index) => &prefix[..index + 1],
+            None => "",
+        };
+        check_relative_path(dir)?;
+
+        let mut names = Vec::new();
+        let root = self.path.join(dir);
+        if root.is_dir() {
+            list_dir(&root, dir, &mut names)?;
+        }
+
+        names.retain(|name| name.starts_with(prefix));
+        names.sort();
+        Ok(names)
+    }
+}
+
+/// Recursively collects the files in `path`, naming them relative to the
+/// store root with `rel` being the relative path of `path` itself.
+fn list_dir(path: &Path, rel: &str, names: &mut Vec<String>) -> io::Result<()> {
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let name = format!("{}{}", rel, entry.file_name().to_string_lossy());
+        if entry.file_type()?.is_dir() {
+            list_dir(&entry.path(), &format!("{}/", name), names)?;
+        } else {
+            names.push(name);
+        }
+    }
+
+    Ok(())
+}
+
+impl AssetLoaderRaw for Image<u8> {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<Image<u8>, LoaderError> {
+        read_from(&mut Cursor::new(data), ColFmt::RGBA).map_err(|e| format!("Invalid image: {:?}", e).into())
+    }
+}
+
+impl AssetLoader<Texture> for Image<u8> {
+    fn from_data(assets: &mut Assets, image: Image<u8>) -> Result<Texture, LoaderError> {
+        let pixels = image.buf.chunks(4).map(|p| [p[0], p[1], p[2], p[3]]).collect::<Vec<_>>();
+
+        AssetLoader::from_data(assets,
+                               TextureLoadData {
+                                   kind: Kind::D2(image.w as u16, image.h as u16, AaMode::Single),
+                                   raw: &[pixels.as_slice()],
+                               })
+    }
+}
+
+impl AssetLoaderRaw for DDS {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<DDS, LoaderError> {
+        DDS::decode(&mut data.clone()).ok_or_else(|| "Invalid DDS data".into())
+    }
+}
+
+impl AssetLoader<Texture> for DDS {
+    fn from_data(assets: &mut Assets, image: DDS) -> Result<Texture, LoaderError> {
+        AssetLoader::from_data(assets,
+                               TextureLoadData {
+                                   kind: Kind::D2(image.header.width as u16,
                                                   image.header.height as u16,
                                                   AaMode::Single),
                                    raw: image.layers
@@ -391,22 +1460,56 @@ impl AssetLoader<Texture> for DDS {
 }
 
 impl AssetLoaderRaw for ObjSet {
-    fn from_raw(_: &Assets, data: &[u8]) -> Option<ObjSet> {
-        if let Some(data) = str::from_utf8(data).ok() {
-            parse(data.into()).ok()
-        } else {
-            None
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<ObjSet, LoaderError> {
+        let data = str::from_utf8(data)?;
+        obj::parse(data.into()).map_err(|e| format!("Invalid OBJ data: {:?}", e).into())
+    }
+
+    /// Selects the object with the given name.
+    fn sub_asset(mut self, fragment: &str) -> Result<ObjSet, LoaderError> {
+        self.objects.retain(|object| object.name == fragment);
+        if self.objects.is_empty() {
+            return Err(format!("No object \"{}\" in OBJ data", fragment).into());
         }
+        Ok(self)
+    }
+}
+
+fn color_to_rgba(color: mtl::Color, alpha: f32) -> [f32; 4] {
+    [color.r as f32, color.g as f32, color.b as f32, alpha]
+}
+
+impl AssetLoaderRaw for MtlSet {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<MtlSet, LoaderError> {
+        let data = str::from_utf8(data)?;
+        mtl::parse(data.into()).map_err(|e| format!("Invalid MTL data: {:?}", e).into())
+    }
+
+    /// Selects the material with the given name.
+    fn sub_asset(mut self, fragment: &str) -> Result<MtlSet, LoaderError> {
+        self.materials.retain(|material| material.name == fragment);
+        if self.materials.is_empty() {
+            return Err(format!("No material \"{}\" in MTL data", fragment).into());
+        }
+        Ok(self)
+    }
+}
+
+impl AssetLoader<ObjMaterial> for MtlSet {
+    /// Takes the first material of the file, use sub-asset names to select a
+    /// material by name.
+    fn from_data(_: &mut Assets, mtl_set: MtlSet) -> Result<ObjMaterial, LoaderError> {
+        mtl_set.materials.into_iter().next().ok_or_else(|| "Empty MTL data".into())
     }
 }
 
 impl AssetLoader<Mesh> for ObjSet {
-    fn from_data(assets: &mut Assets, obj_set: ObjSet) -> Option<Mesh> {
+    fn from_data(assets: &mut Assets, obj_set: ObjSet) -> Result<Mesh, LoaderError> {
         // Takes a list of objects that contain geometries that contain shapes that contain
         // vertex/texture/normal indices into the main list of vertices, and converts to a
         // flat vec of `VertexPosNormal` objects.
-        // TODO: Doesn't differentiate between objects in a `*.obj` file, treats
-        // them all as a single mesh.
+        // Treats all objects in a `*.obj` file as a single mesh, single objects
+        // can be loaded as sub-assets (e.g. `"scene#Cube"`).
         let vertices: Vec<VertexPosNormal> = obj_set.objects
             .iter()
             .flat_map(|object| {
@@ -472,21 +1575,22 @@ impl AssetLoader<Mesh> for ObjSet {
 #[cfg(test)]
 mod tests {
     use super::{Assets, AssetManager, AssetLoader, AssetLoaderRaw};
+    use asset_manager::{AssetErrorKind, AssetStoreError, LoaderError};
 
     #[derive(PartialEq, Debug)]
     struct Foo;
     struct FooLoader;
 
     impl AssetLoader<Foo> for u32 {
-        fn from_data(_: &mut Assets, x: u32) -> Option<Foo> {
-            if x == 10 { Some(Foo) } else { None }
+        fn from_data(_: &mut Assets, x: u32) -> Result<Foo, LoaderError> {
+            if x == 10 { Ok(Foo) } else { Err("Not ten".into()) }
         }
     }
 
     impl AssetLoaderRaw for u32 {
-        fn from_raw(assets: &Assets, _: &[u8]) -> Option<u32> {
+        fn from_raw(assets: &Assets, _: &[u8]) -> Result<u32, LoaderError> {
             let _ = assets.get_loader::<FooLoader>();
-            Some(10)
+            Ok(10)
         }
     }
 
@@ -505,8 +1609,8 @@ mod tests {
         assets.register_loader::<Foo, u32>("foo");
         assets.add_loader::<FooLoader>(FooLoader);
 
-        assert!(assets.load_asset_from_raw::<Foo>("asset01", "foo", &[0; 2]).is_some());
-        assert_eq!(None, assets.load_asset_from_data::<Foo, u32>("foo", 2));
+        assert!(assets.load_asset_from_raw::<Foo>("asset01", "foo", &[0; 2]).is_ok());
+        assert!(assets.load_asset_from_data::<Foo, u32>("foo", 2).is_err());
     }
 
     #[test]
@@ -516,8 +1620,618 @@ mod tests {
         assets.register_loader::<Foo, u32>("foo");
         assets.add_loader::<FooLoader>(FooLoader);
 
-        let asset01 = assets.load_asset_from_raw::<Foo>("asset01", "foo", &[0; 2]);
+        let asset01 = assets.load_asset_from_raw::<Foo>("asset01", "foo", &[0; 2]).ok();
         assert_eq!(asset01,
-                   assets.load_asset_from_raw::<Foo>("asset01", "foo", &[0; 2]));
+                   assets.load_asset_from_raw::<Foo>("asset01", "foo", &[0; 2]).ok());
+    }
+
+    #[test]
+    fn default_asset() {
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        assert_eq!(None, assets.id_from_name_or_default::<Foo>("missing"));
+
+        let default = assets.load_asset_from_raw::<Foo>("default", "foo", &[0; 2]).unwrap();
+        let asset01 = assets.load_asset_from_raw::<Foo>("asset01", "foo", &[0; 2]).unwrap();
+        assets.set_default::<Foo>(default);
+
+        assert_eq!(Some(default), assets.id_from_name_or_default::<Foo>("missing"));
+        assert_eq!(Some(asset01), assets.id_from_name_or_default::<Foo>("asset01"));
+    }
+
+    #[test]
+    fn unload_asset() {
+        use super::AssetReadStorage;
+
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        let asset01 = assets.load_asset_from_raw::<Foo>("asset01", "foo", &[0; 2]).unwrap();
+        assert!(assets.unload_asset("asset01"));
+        assert!(!assets.unload_asset("asset01"));
+
+        assert_eq!(None, assets.id_from_name("asset01"));
+        assert_eq!(None, assets.read_assets::<Foo>().read(asset01));
+    }
+
+    #[test]
+    fn unload_asset_forgets_info() {
+        use asset_manager::EmbeddedStore;
+
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        let mut store = EmbeddedStore::new();
+        store.insert("asset01", "foo", b"10");
+        assets.register_store(store);
+
+        assets.load_asset::<Foo>("asset01", "foo").unwrap();
+        assert!(assets.asset_info("asset01").is_some());
+        assert!(assets.unload_asset("asset01"));
+
+        assets.load_asset_from_data::<Foo, u32>("asset01", 10).unwrap();
+        assert!(assets.asset_info("asset01").is_none());
+    }
+
+    #[test]
+    fn iterate_assets() {
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        let asset01 = assets.load_asset_from_raw::<Foo>("asset01", "foo", &[0; 2]).unwrap();
+        let asset02 = assets.load_asset_from_raw::<Foo>("asset02", "foo", &[0; 2]).unwrap();
+
+        let mut ids = Vec::new();
+        assets.for_each_asset::<Foo, _>(|id, _| ids.push(id));
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&asset01) && ids.contains(&asset02));
+
+        let mut names: Vec<_> = assets.asset_names().collect();
+        names.sort_by_key(|&(name, _)| name);
+        assert_eq!(names, vec![("asset01", asset01), ("asset02", asset02)]);
+    }
+
+    #[test]
+    fn list_directory() {
+        use super::{AssetStore, DirectoryStore};
+
+        let path = format!("{}/examples/05_assets/resources", env!("CARGO_MANIFEST_DIR"));
+        let store = DirectoryStore::new(path);
+
+        let meshes = store.list("meshes/").unwrap();
+        assert!(meshes.contains(&"meshes/cube.obj".to_string()));
+        assert!(meshes.iter().all(|name| name.starts_with("meshes/")));
+
+        assert_eq!(store.list("meshes/cu").unwrap(),
+                   vec!["meshes/cube.obj".to_string(), "meshes/cuboid.custom".to_string()]);
+        assert!(store.list("does_not_exist/").unwrap().is_empty());
+    }
+
+    #[test]
+    fn reject_path_traversal() {
+        use super::{AssetStore, DirectoryStore};
+
+        let path = format!("{}/examples/05_assets/resources", env!("CARGO_MANIFEST_DIR"));
+        for store in vec![DirectoryStore::new(&path), DirectoryStore::sandboxed(&path)] {
+            let mut buf = Vec::new();
+            assert!(store.load_asset("meshes/cube", "obj", &mut buf).is_ok());
+            assert!(store.has_asset("./meshes/cube", "obj"));
+
+            for name in &["../../../Cargo", "meshes/../../../../Cargo", "/etc/hosts", "C:/Windows/win"] {
+                match store.load_asset(name, "toml", &mut buf) {
+                    Err(AssetStoreError::PermissionDenied) => (),
+                    result => panic!("Unexpected result for \"{}\": {:?}", name, result),
+                }
+                assert!(!store.has_asset(name, "toml"));
+            }
+
+            match store.list("../") {
+                Err(AssetStoreError::PermissionDenied) => (),
+                result => panic!("Unexpected result: {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn normalized_paths() {
+        use super::{AssetStore, DirectoryStore, normalize_path};
+
+        assert_eq!(normalize_path("Textures\\Hero.PNG"), "textures/hero.png");
+        assert_eq!(normalize_path("./textures//hero.png"), "textures/hero.png");
+
+        let path = format!("{}/examples/05_assets/resources", env!("CARGO_MANIFEST_DIR"));
+        let mut buf = Vec::new();
+        let store = DirectoryStore::new(&path).with_normalized_paths();
+        assert!(store.has_asset("Meshes\\Cube", "OBJ"));
+        assert!(store.load_asset("Textures/LICENSE", "TXT", &mut buf).is_ok());
+        match store.load_asset("meshes\\..\\..\\..\\Cargo", "toml", &mut buf) {
+            Err(AssetStoreError::PermissionDenied) => (),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn load_errors() {
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+
+        match assets.load_asset_from_raw::<Foo>("asset01", "bar", &[0; 2]) {
+            Err(e) => {
+                assert_eq!(e.name, "asset01");
+                assert_eq!(e.asset_type, Some("bar".to_string()));
+                match e.kind {
+                    AssetErrorKind::UnregisteredAssetType => (),
+                    kind => panic!("Unexpected error kind: {:?}", kind),
+                }
+            }
+            Ok(_) => panic!("Loaded asset of unregistered type"),
+        }
+
+        match assets.load_asset::<Foo>("does_not_exist", "foo") {
+            Err(e) => {
+                match e.kind {
+                    AssetErrorKind::NoSuchAsset => (),
+                    kind => panic!("Unexpected error kind: {:?}", kind),
+                }
+            }
+            Ok(_) => panic!("Loaded non-existent asset"),
+        }
+    }
+
+    #[test]
+    fn load_from_named_store() {
+        use asset_manager::EmbeddedStore;
+
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        let mut store = EmbeddedStore::new();
+        store.insert("asset01", "foo", b"10");
+        assets.register_named_store("dlc1", store);
+
+        assert!(assets.load_asset_from::<Foo>("dlc1", "asset01", "foo").is_ok());
+
+        match assets.load_asset_from::<Foo>("dlc2", "asset01", "foo") {
+            Err(e) => {
+                match e.kind {
+                    AssetErrorKind::UnregisteredStore(ref key) if key == "dlc2" => (),
+                    kind => panic!("Unexpected error kind: {:?}", kind),
+                }
+            }
+            Ok(_) => panic!("Loaded asset from unregistered store"),
+        }
+
+        match assets.load_asset_from::<Foo>("dlc1", "asset02", "foo") {
+            Err(e) => {
+                match e.kind {
+                    AssetErrorKind::Store { error: AssetStoreError::NoSuchAsset, .. } => (),
+                    kind => panic!("Unexpected error kind: {:?}", kind),
+                }
+            }
+            Ok(_) => panic!("Loaded non-existent asset"),
+        }
+    }
+
+    #[test]
+    fn split_path_extension() {
+        use super::split_extension;
+
+        assert_eq!(split_extension("textures/hero.png"), Some(("textures/hero", "png")));
+        assert_eq!(split_extension("hero.tar.gz"), Some(("hero.tar", "gz")));
+        assert_eq!(split_extension("v1.0/hero"), None);
+        assert_eq!(split_extension("textures/.hidden"), None);
+    }
+
+    #[test]
+    fn resolve_relative_paths() {
+        use super::relative_path;
+
+        assert_eq!(relative_path("maps/level1", "tiles.png"), "maps/tiles.png");
+        assert_eq!(relative_path("maps/level1", "../tilesets/grass.png"), "tilesets/grass.png");
+        assert_eq!(relative_path("maps/level1", "./tiles.png"), "maps/tiles.png");
+        assert_eq!(relative_path("level1", "../secret.png"), "../secret.png");
+        assert_eq!(relative_path("maps/level1", "../../secret.png"), "../secret.png");
+    }
+
+    #[test]
+    fn obj_sub_asset() {
+        use super::split_sub_asset;
+        use wavefront_obj::obj::ObjSet;
+
+        assert_eq!(split_sub_asset("models/scene#Cube"), ("models/scene", Some("Cube")));
+        assert_eq!(split_sub_asset("models/scene"), ("models/scene", None));
+
+        let assets = AssetManager::new();
+        let data = b"o Cube\nv 0 0 0\no Plane\nv 1 1 1\n";
+        let obj = ObjSet::from_raw(&assets, data).unwrap().sub_asset("Plane").unwrap();
+        assert_eq!(obj.objects.len(), 1);
+        assert_eq!(obj.objects[0].name, "Plane");
+
+        assert!(ObjSet::from_raw(&assets, data).unwrap().sub_asset("Sphere").is_err());
+    }
+
+    #[test]
+    fn load_mtl_material() {
+        use asset_manager::EmbeddedStore;
+        use ecs::components::Texture;
+        use renderer;
+
+        let mut store = EmbeddedStore::new();
+        store.insert("materials/cube",
+                     "mtl",
+                     b"newmtl Red\nNs 10\nKa 0.1 0 0\nKd 1 0 0\nKs 0.5 0.5 0.5\nd 1\nillum 2\n\
+                       newmtl Blue\nNs 10\nKa 0 0 0.1\nKd 0 0 1\nKs 0.5 0.5 0.5\nd 1\nillum 2\n");
+
+        let mut assets = AssetManager::new();
+        assets.register_store(store);
+        assets.load_mtl_material("materials/cube#Blue").unwrap();
+        assert!(assets.load_mtl_material("materials/cube#Green").is_err());
+
+        let id = assets.id_from_name("materials/cube#Blue.kd").unwrap();
+        match assets.read_assets::<Texture>().get(id) {
+            Some(&super::Asset(renderer::Texture::Constant(color))) => {
+                assert_eq!(color, [0.0, 0.0, 1.0, 1.0])
+            }
+            _ => panic!("Expected constant diffuse texture"),
+        }
+    }
+
+    #[test]
+    fn load_obj_materials() {
+        use asset_manager::{AssetErrorKind, EmbeddedStore};
+        use ecs::components::Texture;
+
+        let mut store = EmbeddedStore::new();
+        store.insert("models/cube",
+                     "obj",
+                     b"mtllib cube.mtl\no Cube\nv 0 0 0\nv 1 0 0\nv 0 1 0\nusemtl Red\nf 1 2 3\n");
+        store.insert("models/cube",
+                     "mtl",
+                     b"newmtl Red\nNs 10\nKa 0.1 0 0\nKd 1 0 0\nKs 0.5 0.5 0.5\nd 1\nillum 2\n\
+                       newmtl Blue\nNs 10\nKa 0 0 0.1\nKd 0 0 1\nKs 0.5 0.5 0.5\nd 1\nillum 2\n");
+
+        let mut assets = AssetManager::new();
+        assets.register_store(store);
+        let ids = assets.load_obj_materials("models/cube").unwrap();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(assets.id_from_name("models/cube#Red"), Some(ids[0]));
+        assert!(assets.id_from_name("models/cube#Red.kd").is_some());
+
+        assets.load_asset_from_data::<Texture, [f32; 4]>("models/cube#Blue", [1.0; 4]).unwrap();
+        match assets.load_mtl_material("models/cube#Blue") {
+            Err(e) => {
+                match e.kind {
+                    AssetErrorKind::NameTaken => (),
+                    kind => panic!("Unexpected error kind: {:?}", kind),
+                }
+            }
+            Ok(_) => panic!("Loaded material over an existing texture"),
+        }
+    }
+
+    #[test]
+    fn load_audio() {
+        use ecs::components::{AudioFormat, AudioSource};
+
+        let mut assets = AssetManager::new();
+        let id = assets.load_asset_from_raw::<AudioSource>("beep", "ogg", b"OggS\0\x02").unwrap();
+        assert_eq!(assets.read_assets::<AudioSource>().get(id).map(|asset| asset.0.format),
+                   Some(AudioFormat::Ogg));
+
+        assert!(assets.load_asset_from_raw::<AudioSource>("beep", "wav", b"RIFF").is_err());
+        assert_eq!(AudioFormat::detect(b"RIFF\0\0\0\0WAVEfmt "), Some(AudioFormat::Wav));
+        assert_eq!(AudioFormat::detect(b"fLaC\0"), Some(AudioFormat::Flac));
+    }
+
+    #[test]
+    fn load_font() {
+        use ecs::components::{Font, FontFormat};
+
+        let mut assets = AssetManager::new();
+        let id = assets.load_asset_from_raw::<Font>("sans", "otf", b"OTTO\0\x0a").unwrap();
+        assert_eq!(assets.read_assets::<Font>().get(id).map(|asset| asset.0.format),
+                   Some(FontFormat::OpenType));
+
+        assert!(assets.load_asset_from_raw::<Font>("serif", "ttf", b"wOFF").is_err());
+        assert_eq!(FontFormat::detect(&[0, 1, 0, 0, 0, 0x0a]), Some(FontFormat::TrueType));
+    }
+
+    #[cfg(feature="serde_json")]
+    #[test]
+    fn load_sprite_sheet_data() {
+        use ecs::components::{Sprite, SpriteSheet};
+
+        let mut assets = AssetManager::new();
+        let data = br#"{
+            "frames": {
+                "hero.png": { "frame": { "x": 0, "y": 0, "w": 16, "h": 32 } },
+                "sword.png": { "frame": { "x": 16, "y": 0, "w": 8, "h": 8 } }
+            },
+            "meta": { "image": "sheet.png", "size": { "w": 64, "h": 64 } }
+        }"#;
+        let id = assets.load_asset_from_raw::<SpriteSheet>("sheet", "json", data).unwrap();
+
+        let sheets = assets.read_assets::<SpriteSheet>();
+        let sheet = &sheets.get(id).unwrap().0;
+        assert_eq!(sheet.texture, "sheet.png");
+        assert_eq!(sheet.sprite("sword.png"),
+                   Some(&Sprite {
+                       name: "sword.png".into(),
+                       x: 16,
+                       y: 0,
+                       width: 8,
+                       height: 8,
+                   }));
+    }
+
+    #[cfg(feature="tiled")]
+    #[test]
+    fn load_tile_map_data() {
+        use ecs::components::TileMap;
+
+        let mut assets = AssetManager::new();
+        let data = br#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.0" orientation="orthogonal" width="2" height="1" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" name="ground" tilewidth="16" tileheight="16">
+  <image source="ground.png" width="32" height="16"/>
+ </tileset>
+ <layer name="floor" width="2" height="1">
+  <data encoding="csv">1,2</data>
+ </layer>
+</map>"#;
+        let id = assets.load_asset_from_raw::<TileMap>("level01", "tmx", data).unwrap();
+
+        let maps = assets.read_assets::<TileMap>();
+        let map = &maps.get(id).unwrap().0;
+        assert_eq!(map.tilesets[0].images[0].source, "ground.png");
+        assert_eq!(map.layers[0].name, "floor");
+    }
+
+    #[test]
+    fn load_shader_includes() {
+        use asset_manager::EmbeddedStore;
+        use ecs::components::ShaderSource;
+
+        let mut store = EmbeddedStore::new();
+        store.insert("shaders/main", "frag", b"#version 150\n#include \"lib/light.glsl\"\nvoid main() {}\n");
+        store.insert("shaders/lib/light", "glsl", b"#include \"common.glsl\"\nfloat light;\n");
+        store.insert("shaders/lib/common", "glsl", b"float common;\n");
+        store.insert("shaders/loop", "glsl", b"#include \"loop.glsl\"\n");
+
+        let mut assets = AssetManager::new();
+        assets.register_store(store);
+        let id = assets.load_shader("shaders/main", "frag").unwrap();
+
+        let shaders = assets.read_assets::<ShaderSource>();
+        let shader = &shaders.get(id).unwrap().0;
+        assert_eq!(shader.source, "#version 150\nfloat common;\nfloat light;\nvoid main() {}\n");
+        assert_eq!(shader.includes,
+                   vec!["shaders/lib/light.glsl".to_string(), "shaders/lib/common.glsl".to_string()]);
+        drop(shaders);
+
+        assert!(assets.load_shader("shaders/loop", "glsl").is_err());
+    }
+
+    #[test]
+    fn insert_asset() {
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+
+        let first = assets.insert_asset(Foo).unwrap();
+        let second = assets.insert_asset(Foo).unwrap();
+        assert!(first != second);
+        assert!(assets.read_assets::<Foo>().get(first).is_some());
+        assert_eq!(assets.asset_names().count(), 0);
+
+        match assets.insert_asset(0u32) {
+            Err(e) => {
+                match e.kind {
+                    AssetErrorKind::UnregisteredAssetType => (),
+                    kind => panic!("Unexpected error kind: {:?}", kind),
+                }
+            }
+            Ok(_) => panic!("Inserted asset of unregistered type"),
+        }
+    }
+
+    #[test]
+    fn load_asset_batch() {
+        use asset_manager::{EmbeddedStore, ProgressCounter};
+
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        let mut store = EmbeddedStore::new();
+        store.insert("asset01", "foo", b"10");
+        store.insert("asset02", "foo", b"10");
+        assets.register_store(store);
+
+        let mut progress = ProgressCounter::new();
+        let ids = assets.load_assets::<Foo>(&[("asset01", "foo"), ("missing", "foo"), ("asset02", "foo")],
+                                            &mut progress);
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[0].as_ref().ok(), assets.id_from_name("asset01").as_ref());
+        assert!(ids[1].is_err());
+        assert_eq!(ids[2].as_ref().ok(), assets.id_from_name("asset02").as_ref());
+
+        assert_eq!(progress.num_assets(), 3);
+        assert_eq!(progress.num_finished(), 2);
+        assert_eq!(progress.num_failed(), 1);
+
+        // Announcing the batch up front doesn't count it twice.
+        let mut progress = ProgressCounter::new();
+        progress.add_assets(2);
+        assets.load_assets::<Foo>(&[("asset01", "foo"), ("asset02", "foo")], &mut progress);
+        assert_eq!(progress.num_assets(), 2);
+        assert!(progress.is_complete());
+    }
+
+    #[test]
+    fn record_stats() {
+        use asset_manager::EmbeddedStore;
+
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        let mut store = EmbeddedStore::new();
+        store.insert("asset01", "foo", b"10");
+        assets.register_store(store);
+
+        assets.load_asset::<Foo>("asset01", "foo").unwrap();
+        assert!(assets.load_asset::<Foo>("missing", "foo").is_err());
+        assert!(assets.load_asset_from_raw::<Foo>("asset02", "bar", b"10").is_err());
+
+        let foo = *assets.stats().get("foo").unwrap();
+        assert_eq!(foo.num_loaded, 1);
+        assert_eq!(foo.num_failed, 1);
+        assert_eq!(foo.num_bytes, 2);
+        assert_eq!(assets.stats().get("bar").unwrap().num_failed, 1);
+
+        assets.stats_mut().clear();
+        assert_eq!(assets.stats().total().num_loaded, 0);
+    }
+
+    #[test]
+    fn deduplicate_loads() {
+        use asset_manager::{EmbeddedStore, ProgressCounter};
+
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        let mut store = EmbeddedStore::new();
+        store.insert("asset01", "foo", b"10");
+        store.insert("asset02", "foo", b"10");
+        assets.register_store(store);
+
+        let first = assets.load_asset::<Foo>("asset01", "foo").unwrap();
+        assert_eq!(assets.load_asset::<Foo>("asset01", "foo").unwrap(), first);
+
+        let mut progress = ProgressCounter::new();
+        let ids = assets.load_assets::<Foo>(&[("asset01", "foo"), ("asset02", "foo"), ("asset02", "foo")],
+                                            &mut progress);
+        assert_eq!(ids[0].as_ref().ok(), Some(&first));
+        assert_eq!(ids[1].as_ref().ok(), ids[2].as_ref().ok());
+        assert_eq!(progress.num_finished(), 3);
+
+        assert_eq!(assets.stats().get("foo").unwrap().num_loaded, 2);
+    }
+
+    #[test]
+    fn query_load_state() {
+        use super::LoadState;
+
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        assert_eq!(assets.load_state("asset01"), LoadState::NotLoaded);
+        assert!(assets.load_asset::<Foo>("asset01", "foo").is_err());
+        match assets.load_state("asset01") {
+            LoadState::Failed(error) => assert!(error.contains("not found in any asset store")),
+            state => panic!("Unexpected load state: {:?}", state),
+        }
+
+        let id = assets.load_asset_from_raw::<Foo>("asset01", "foo", &[0; 2]).unwrap();
+        assert_eq!(assets.load_state("asset01"), LoadState::Loaded(id));
+    }
+
+    #[test]
+    fn resolve_aliases() {
+        use asset_manager::EmbeddedStore;
+
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        let mut store = EmbeddedStore::new();
+        store.insert("skins/default/body", "foo", b"10");
+        store.insert("skins", "aliases", b"player_skin: \"skins/default/body\"\n");
+        assets.register_store(store);
+
+        assert_eq!(assets.load_aliases("skins").unwrap(), 1);
+        assert_eq!(assets.resolve_alias("player_skin"), "skins/default/body");
+        assert_eq!(assets.resolve_alias("enemy_skin"), "enemy_skin");
+
+        let id = assets.load_asset::<Foo>("player_skin", "foo").unwrap();
+        assert_eq!(assets.id_from_name("skins/default/body"), Some(id));
+        assert_eq!(assets.id_from_name("player_skin"), Some(id));
+
+        assets.add_alias("player_skin", "skins/winter/body");
+        assert_eq!(assets.id_from_name("player_skin"), None);
+    }
+
+    #[test]
+    fn preload_list() {
+        use super::LoadState;
+        use asset_manager::{EmbeddedStore, ProgressCounter};
+
+        let mut assets = AssetManager::new();
+        assets.register_asset::<Foo>();
+        assets.register_loader::<Foo, u32>("foo");
+        assets.add_loader::<FooLoader>(FooLoader);
+
+        let mut store = EmbeddedStore::new();
+        store.insert("asset01", "foo", b"10");
+        store.insert("level1", "preload", b"assets:\n  - [\"asset01\", \"foo\"]\n  - [\"missing\", \"foo\"]\n");
+        assets.register_store(store);
+
+        let mut progress = ProgressCounter::new();
+        assets.preload("level1", &mut progress).unwrap();
+        assert_eq!(progress.num_finished(), 1);
+        assert_eq!(progress.num_failed(), 1);
+        assert!(progress.is_complete());
+
+        assert!(assets.id_from_name("asset01").is_some());
+        match assets.load_state("missing") {
+            LoadState::Failed(_) => (),
+            state => panic!("Unexpected load state: {:?}", state),
+        }
+        assert!(assets.preload("level2", &mut progress).is_err());
+    }
+
+    #[test]
+    fn inspect_dependencies() {
+        use asset_manager::{EmbeddedStore, Prefab};
+
+        let mut store = EmbeddedStore::new();
+        store.insert("wheel", "prefab", b"entities:\n  - translation: [1.0, 0.0, 0.0]\n");
+        store.insert("cart", "prefab", b"entities:\n  - prefab: \"wheel\"\n");
+
+        let mut assets = AssetManager::new();
+        assets.register_store(store);
+        assets.load_prefab("cart").unwrap();
+
+        let cart = assets.asset_info("cart").unwrap();
+        assert_eq!(cart.asset_type, "prefab");
+        assert_eq!(cart.store, Some("embedded assets".to_string()));
+        assert_eq!(cart.dependencies, vec!["wheel".to_string()]);
+        assert_eq!(assets.dependents("wheel"), vec!["cart"]);
+
+        assets.load_asset_from_raw::<Prefab>("bike", "prefab", b"entities: []").unwrap();
+        assert_eq!(assets.asset_info("bike").unwrap().store, None);
+
+        assets.unload_asset("cart");
+        assert!(assets.asset_info("cart").is_none());
+        assert!(assets.dependents("wheel").is_empty());
     }
 }
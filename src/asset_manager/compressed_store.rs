@@ -0,0 +1,147 @@
+//! Asset store wrapper decompressing the assets of another asset store.
+
+use std::io;
+#[cfg(feature="lz4")]
+use std::io::{Read, Write};
+
+use asset_manager::{AssetStore, AssetStoreError};
+
+/// Magic bytes starting the header of a compressed asset.
+const MAGIC: &'static [u8; 3] = b"AMZ";
+
+/// Compression methods understood by `CompressedStore`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Compression {
+    /// [Zstandard](https://facebook.github.io/zstd/) compression. Requires
+    /// the `zstd` feature.
+    Zstd,
+    /// [LZ4](https://lz4.github.io/lz4/) frame compression. Requires the
+    /// `lz4` feature.
+    Lz4,
+}
+
+impl Compression {
+    /// Returns the header which has to precede an asset compressed with this
+    /// method.
+    pub fn header(&self) -> [u8; 4] {
+        let id = match *self {
+            Compression::Zstd => 1,
+            Compression::Lz4 => 2,
+        };
+
+        [MAGIC[0], MAGIC[1], MAGIC[2], id]
+    }
+
+    fn from_header(data: &[u8]) -> Option<Compression> {
+        if data.len() < 4 || &data[..3] != MAGIC {
+            return None;
+        }
+
+        match data[3] {
+            1 => Some(Compression::Zstd),
+            2 => Some(Compression::Lz4),
+            _ => None,
+        }
+    }
+
+    /// Compresses data with this method. The header isn't included.
+    pub fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match *self {
+            #[cfg(feature="zstd")]
+            Compression::Zstd => ::zstd::encode_all(data, 0),
+            #[cfg(feature="lz4")]
+            Compression::Lz4 => {
+                let mut encoder = ::lz4::EncoderBuilder::new().build(Vec::new())?;
+                encoder.write_all(data)?;
+                let (compressed, result) = encoder.finish();
+                result.map(|_| compressed)
+            }
+            #[allow(unreachable_patterns)]
+            method => {
+                let msg = format!("Support for {:?} compression is not enabled", method);
+                Err(io::Error::new(io::ErrorKind::Other, msg))
+            }
+        }
+    }
+
+    fn decompress(&self, data: &[u8], buf: &mut Vec<u8>) -> io::Result<usize> {
+        match *self {
+            #[cfg(feature="zstd")]
+            Compression::Zstd => {
+                let decompressed = ::zstd::decode_all(data)?;
+                buf.extend_from_slice(&decompressed);
+                Ok(decompressed.len())
+            }
+            #[cfg(feature="lz4")]
+            Compression::Lz4 => ::lz4::Decoder::new(data)?.read_to_end(buf),
+            #[allow(unreachable_patterns)]
+            method => {
+                let msg = format!("Support for {:?} compression is not enabled", method);
+                Err(io::Error::new(io::ErrorKind::Other, msg))
+            }
+        }
+    }
+}
+
+/// Asset store wrapper which transparently decompresses the assets of the
+/// store `S`, so packed builds can trade CPU time for disk size without
+/// changes to any asset loader.
+///
+/// Compressed assets start with the header returned by
+/// `Compression::header`, followed by the compressed data. Assets without
+/// such a header are passed through unchanged.
+pub struct CompressedStore<S> {
+    inner: S,
+}
+
+impl<S: AssetStore> CompressedStore<S> {
+    /// Creates a new compressed store reading from `inner`.
+    pub fn new(inner: S) -> CompressedStore<S> {
+        CompressedStore { inner: inner }
+    }
+}
+
+impl<S: AssetStore> AssetStore for CompressedStore<S> {
+    fn name(&self) -> String {
+        format!("compressed {}", self.inner.name())
+    }
+
+    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
+        self.inner.has_asset(name, asset_type)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, AssetStoreError> {
+        self.inner.list(prefix)
+    }
+
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+        let mut data = Vec::new();
+        let len = self.inner.load_asset(name, asset_type, &mut data)?;
+
+        match Compression::from_header(&data) {
+            Some(method) => Ok(method.decompress(&data[4..], buf)?),
+            None => {
+                buf.extend_from_slice(&data);
+                Ok(len)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Compression;
+
+    #[test]
+    fn headers() {
+        for &method in &[Compression::Zstd, Compression::Lz4] {
+            let mut data = method.header().to_vec();
+            data.extend_from_slice(b"data");
+            assert_eq!(Compression::from_header(&data), Some(method));
+        }
+
+        assert_eq!(Compression::from_header(b"AMZ"), None);
+        assert_eq!(Compression::from_header(b"AMZ\x00data"), None);
+        assert_eq!(Compression::from_header(b"data"), None);
+    }
+}
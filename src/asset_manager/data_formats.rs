@@ -0,0 +1,470 @@
+//! Asset loaders for data assets deserialized with serde.
+
+use serde::{Deserialize, Deserializer};
+use serde::de::{DeserializeOwned, Error, IgnoredAny, MapAccess, SeqAccess, Visitor};
+#[cfg(feature="bincode")]
+use serde::Serialize;
+use std::fmt;
+use std::marker::PhantomData;
+#[cfg(any(feature="ron", feature="toml"))]
+use std::str;
+
+use asset_manager::{AssetLoader, AssetLoaderRaw, Assets, LoaderError};
+
+/// A serde data format, implemented by the data format loaders like `Ron`.
+pub trait DataFormat {
+    /// The data type the loader produces.
+    type Data;
+
+    /// Deserializes any type from data in this format.
+    fn parse<T: DeserializeOwned>(data: &[u8]) -> Result<T, LoaderError>;
+
+    /// Reads the version number of `Versioned` data, see `Versioned`. The
+    /// default reads a `version` field next to the data, which only works for
+    /// self-describing formats.
+    fn parse_version(data: &[u8]) -> Result<u32, LoaderError> {
+        Ok(Self::parse::<Envelope<IgnoredAny>>(data)?.version)
+    }
+}
+
+/// Asset loader for any serde-deserializable data type `D` stored in
+/// [RON](https://github.com/ron-rs/ron) files, so config-like assets don't
+/// need a loader of their own. Requires the `ron` feature.
+///
+/// ```ignore
+/// #[derive(Deserialize)]
+/// struct Weapon {
+///     damage: u32,
+///     range: f32,
+/// }
+///
+/// assets.register_asset::<Weapon>();
+/// assets.register_loader::<Weapon, Ron<Weapon>>("ron");
+/// let sword = assets.load_asset::<Weapon>("weapons/sword", "ron");
+/// ```
+#[cfg(feature="ron")]
+pub struct Ron<D>(pub D);
+
+#[cfg(feature="ron")]
+impl<D> DataFormat for Ron<D> {
+    type Data = D;
+
+    fn parse<T: DeserializeOwned>(data: &[u8]) -> Result<T, LoaderError> {
+        let data = str::from_utf8(data)?;
+        Ok(::ron::de::from_str(data)?)
+    }
+}
+
+#[cfg(feature="ron")]
+impl<D: DeserializeOwned> AssetLoaderRaw for Ron<D> {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<Ron<D>, LoaderError> {
+        Ok(Ron(Self::parse(data)?))
+    }
+}
+
+#[cfg(feature="ron")]
+impl<D> AssetLoader<D> for Ron<D> {
+    fn from_data(_: &mut Assets, data: Ron<D>) -> Result<D, LoaderError> {
+        Ok(data.0)
+    }
+}
+
+/// Asset loader for any serde-deserializable data type `D` stored in JSON
+/// files. Requires the `serde_json` feature, see `Ron` for an example.
+#[cfg(feature="serde_json")]
+pub struct Json<D>(pub D);
+
+#[cfg(feature="serde_json")]
+impl<D> DataFormat for Json<D> {
+    type Data = D;
+
+    fn parse<T: DeserializeOwned>(data: &[u8]) -> Result<T, LoaderError> {
+        Ok(::serde_json::from_slice(data)?)
+    }
+}
+
+#[cfg(feature="serde_json")]
+impl<D: DeserializeOwned> AssetLoaderRaw for Json<D> {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<Json<D>, LoaderError> {
+        Ok(Json(Self::parse(data)?))
+    }
+}
+
+#[cfg(feature="serde_json")]
+impl<D> AssetLoader<D> for Json<D> {
+    fn from_data(_: &mut Assets, data: Json<D>) -> Result<D, LoaderError> {
+        Ok(data.0)
+    }
+}
+
+/// Asset loader for any serde-deserializable data type `D` stored in TOML
+/// files. Requires the `toml` feature, see `Ron` for an example.
+#[cfg(feature="toml")]
+pub struct Toml<D>(pub D);
+
+#[cfg(feature="toml")]
+impl<D> DataFormat for Toml<D> {
+    type Data = D;
+
+    fn parse<T: DeserializeOwned>(data: &[u8]) -> Result<T, LoaderError> {
+        let data = str::from_utf8(data)?;
+        Ok(::toml::from_str(data)?)
+    }
+}
+
+#[cfg(feature="toml")]
+impl<D: DeserializeOwned> AssetLoaderRaw for Toml<D> {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<Toml<D>, LoaderError> {
+        Ok(Toml(Self::parse(data)?))
+    }
+}
+
+#[cfg(feature="toml")]
+impl<D> AssetLoader<D> for Toml<D> {
+    fn from_data(_: &mut Assets, data: Toml<D>) -> Result<D, LoaderError> {
+        Ok(data.0)
+    }
+}
+
+/// Asset loader for any serde-deserializable data type `D` stored in the
+/// binary [bincode](https://github.com/TyOverby/bincode) format. Requires
+/// the `bincode` feature.
+///
+/// Bincode is much faster to load than text formats, which makes it a good
+/// fit for shipping pre-cooked versions of RON or JSON data assets:
+///
+/// ```ignore
+/// let weapon = Ron::<Weapon>::from_raw(&assets, &source)?.0;
+/// file.write_all(&Bincode(weapon).to_bytes()?)?;
+/// ```
+#[cfg(feature="bincode")]
+pub struct Bincode<D>(pub D);
+
+#[cfg(feature="bincode")]
+impl<D: Serialize> Bincode<D> {
+    /// Serializes the data, so it can be loaded back with this loader.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, LoaderError> {
+        Ok(::bincode::serialize(&self.0, ::bincode::Infinite)?)
+    }
+}
+
+#[cfg(feature="bincode")]
+impl<D> DataFormat for Bincode<D> {
+    type Data = D;
+
+    fn parse<T: DeserializeOwned>(data: &[u8]) -> Result<T, LoaderError> {
+        Ok(::bincode::deserialize(data)?)
+    }
+
+    fn parse_version(data: &[u8]) -> Result<u32, LoaderError> {
+        // Bincode can't skip the data without knowing its type, so only the
+        // leading version number is read.
+        Self::parse::<u32>(data)
+    }
+}
+
+#[cfg(feature="bincode")]
+impl<D: DeserializeOwned> AssetLoaderRaw for Bincode<D> {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<Bincode<D>, LoaderError> {
+        Ok(Bincode(Self::parse(data)?))
+    }
+}
+
+#[cfg(feature="bincode")]
+impl<D> AssetLoader<D> for Bincode<D> {
+    fn from_data(_: &mut Assets, data: Bincode<D>) -> Result<D, LoaderError> {
+        Ok(data.0)
+    }
+}
+
+/// A data asset type whose format changed over time. Files in the format of
+/// an older version are deserialized as that version and migrated up to the
+/// current one, so old assets keep loading. Load versioned types with the
+/// `Migrated` loader.
+///
+/// Versioned files store the version they were written in next to the data,
+/// like `(version: 2, data: (damage: 10, range: 1.5))` in RON. Binary formats
+/// store the version first, then the data.
+///
+/// ```ignore
+/// impl Versioned for WeaponV1 {
+///     type Previous = FirstVersion;
+///
+///     fn version() -> u32 {
+///         1
+///     }
+///
+///     fn migrate(previous: FirstVersion) -> Result<WeaponV1, LoaderError> {
+///         match previous {}
+///     }
+/// }
+///
+/// impl Versioned for Weapon {
+///     type Previous = WeaponV1;
+///
+///     fn version() -> u32 {
+///         2
+///     }
+///
+///     fn migrate(previous: WeaponV1) -> Result<Weapon, LoaderError> {
+///         Ok(Weapon { damage: previous.damage, range: 1.0 })
+///     }
+/// }
+///
+/// assets.register_loader::<Weapon, Migrated<Ron<Weapon>>>("ron");
+/// ```
+pub trait Versioned: DeserializeOwned {
+    /// The previous version of this type, or `FirstVersion` if there is none.
+    type Previous: Versioned;
+
+    /// The version number stored with data of this version. Has to be
+    /// greater than the version number of `Previous`.
+    fn version() -> u32;
+
+    /// Upgrades data of the previous version.
+    fn migrate(previous: Self::Previous) -> Result<Self, LoaderError>;
+
+    /// Deserializes data of this or any previous version, migrating it up to
+    /// this version.
+    fn load<F: DataFormat>(data: &[u8]) -> Result<Self, LoaderError> {
+        let version = F::parse_version(data)?;
+        Self::load_version::<F>(version, data)
+    }
+
+    /// Deserializes data stored with the given version number, migrating it
+    /// up to this version.
+    fn load_version<F: DataFormat>(version: u32, data: &[u8]) -> Result<Self, LoaderError> {
+        if version == Self::version() {
+            Ok(F::parse::<Envelope<Self>>(data)?.data)
+        } else if version < Self::version() {
+            Self::migrate(Self::Previous::load_version::<F>(version, data)?)
+        } else {
+            let newest = Self::version();
+            Err(format!("Unsupported version {}, the newest version is {}", version, newest).into())
+        }
+    }
+}
+
+/// Versioned data together with the version number it was stored in.
+struct Envelope<T> {
+    version: u32,
+    data: T,
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Envelope<T> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Envelope<T>, De::Error> {
+        let fields = &["version", "data"];
+        deserializer.deserialize_struct("Versioned", fields, EnvelopeVisitor(PhantomData))
+    }
+}
+
+struct EnvelopeVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for EnvelopeVisitor<T> {
+    type Value = Envelope<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a `version` and a `data` field")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Envelope<T>, A::Error> {
+        let version = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let data = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        Ok(Envelope {
+            version: version,
+            data: data,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Envelope<T>, A::Error> {
+        let mut version = None;
+        let mut data = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "version" => version = Some(map.next_value()?),
+                "data" => data = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(Envelope {
+            version: version.ok_or_else(|| A::Error::missing_field("version"))?,
+            data: data.ok_or_else(|| A::Error::missing_field("data"))?,
+        })
+    }
+}
+
+/// Marks the first version of a `Versioned` type as having no previous
+/// version.
+pub enum FirstVersion {}
+
+impl<'de> Deserialize<'de> for FirstVersion {
+    fn deserialize<De: Deserializer<'de>>(_: De) -> Result<FirstVersion, De::Error> {
+        Err(De::Error::custom("no previous version"))
+    }
+}
+
+impl Versioned for FirstVersion {
+    type Previous = FirstVersion;
+
+    fn version() -> u32 {
+        0
+    }
+
+    fn migrate(previous: FirstVersion) -> Result<FirstVersion, LoaderError> {
+        match previous {}
+    }
+
+    fn load_version<F: DataFormat>(version: u32, _: &[u8]) -> Result<FirstVersion, LoaderError> {
+        Err(format!("Unsupported version {}, older than the first version", version).into())
+    }
+}
+
+/// Asset loader for `Versioned` data types stored in the data format `F`,
+/// like `Migrated<Ron<Weapon>>`. See `Versioned`.
+pub struct Migrated<F: DataFormat>(pub F::Data, PhantomData<F>);
+
+impl<F: DataFormat> AssetLoaderRaw for Migrated<F>
+    where F::Data: Versioned
+{
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<Migrated<F>, LoaderError> {
+        Ok(Migrated(F::Data::load::<F>(data)?, PhantomData))
+    }
+}
+
+impl<F: DataFormat> AssetLoader<F::Data> for Migrated<F> {
+    fn from_data(_: &mut Assets, data: Migrated<F>) -> Result<F::Data, LoaderError> {
+        Ok(data.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FirstVersion, Versioned};
+    use asset_manager::{AssetLoaderRaw, AssetManager, LoaderError};
+
+    type Table = Vec<(String, u32)>;
+
+    fn table() -> Table {
+        vec![("sword".into(), 10), ("bow".into(), 7)]
+    }
+
+    #[cfg(feature="ron")]
+    #[test]
+    fn load_ron() {
+        use super::Ron;
+
+        let assets = AssetManager::new();
+        let data = br#"[("sword", 10), ("bow", 7)]"#;
+        assert_eq!(Ron::<Table>::from_raw(&assets, data).unwrap().0, table());
+        assert!(Ron::<Table>::from_raw(&assets, b"[(sword, 10)]").is_err());
+    }
+
+    #[cfg(feature="serde_json")]
+    #[test]
+    fn load_json() {
+        use super::Json;
+
+        let assets = AssetManager::new();
+        let data = br#"[["sword", 10], ["bow", 7]]"#;
+        assert_eq!(Json::<Table>::from_raw(&assets, data).unwrap().0, table());
+        assert!(Json::<Table>::from_raw(&assets, b"[[sword, 10]]").is_err());
+    }
+
+    #[cfg(feature="toml")]
+    #[test]
+    fn load_toml() {
+        use std::collections::BTreeMap;
+        use super::Toml;
+
+        let assets = AssetManager::new();
+        let data = b"sword = 10\nbow = 7\n";
+        let loaded = Toml::<BTreeMap<String, u32>>::from_raw(&assets, data).unwrap().0;
+        let mut expected = table();
+        expected.sort();
+        assert_eq!(loaded.into_iter().collect::<Table>(), expected);
+    }
+
+    #[cfg(feature="bincode")]
+    #[test]
+    fn bincode_round_trip() {
+        use super::Bincode;
+
+        let assets = AssetManager::new();
+        let data = Bincode(table()).to_bytes().unwrap();
+        assert_eq!(Bincode::<Table>::from_raw(&assets, &data).unwrap().0, table());
+        assert!(Bincode::<Table>::from_raw(&assets, &data[..3]).is_err());
+    }
+
+    // Version 1 only stored the damage of every item.
+    type TableV1 = Vec<u32>;
+
+    impl Versioned for TableV1 {
+        type Previous = FirstVersion;
+
+        fn version() -> u32 {
+            1
+        }
+
+        fn migrate(previous: FirstVersion) -> Result<TableV1, LoaderError> {
+            match previous {}
+        }
+    }
+
+    impl Versioned for Table {
+        type Previous = TableV1;
+
+        fn version() -> u32 {
+            2
+        }
+
+        fn migrate(previous: TableV1) -> Result<Table, LoaderError> {
+            Ok(previous.into_iter()
+                .enumerate()
+                .map(|(i, damage)| (format!("item{}", i), damage))
+                .collect())
+        }
+    }
+
+    fn migrated_table() -> Table {
+        vec![("item0".to_string(), 10), ("item1".to_string(), 7)]
+    }
+
+    #[cfg(feature="serde_json")]
+    #[test]
+    fn migrate_old_versions() {
+        use super::{Json, Migrated};
+
+        let assets = AssetManager::new();
+        let current = br#"{"version": 2, "data": [["sword", 10], ["bow", 7]]}"#;
+        assert_eq!(Migrated::<Json<Table>>::from_raw(&assets, current).unwrap().0, table());
+        let old = br#"{"version": 1, "data": [10, 7]}"#;
+        assert_eq!(Migrated::<Json<Table>>::from_raw(&assets, old).unwrap().0, migrated_table());
+
+        let unversioned = br#"{"data": [10, 7]}"#;
+        assert!(Migrated::<Json<Table>>::from_raw(&assets, unversioned).is_err());
+        let newer = br#"{"version": 3, "data": []}"#;
+        assert!(Migrated::<Json<Table>>::from_raw(&assets, newer).is_err());
+        let older = br#"{"version": 0, "data": []}"#;
+        assert!(Migrated::<Json<Table>>::from_raw(&assets, older).is_err());
+    }
+
+    #[cfg(feature="bincode")]
+    #[test]
+    fn migrate_old_bincode_versions() {
+        use super::{Bincode, Migrated};
+
+        let assets = AssetManager::new();
+        let current = Bincode((2u32, table())).to_bytes().unwrap();
+        assert_eq!(Migrated::<Bincode<Table>>::from_raw(&assets, &current).unwrap().0, table());
+        let old = Bincode((1u32, vec![10u32, 7])).to_bytes().unwrap();
+        assert_eq!(Migrated::<Bincode<Table>>::from_raw(&assets, &old).unwrap().0,
+                   migrated_table());
+
+        let newer = Bincode((3u32, Vec::<u32>::new())).to_bytes().unwrap();
+        assert!(Migrated::<Bincode<Table>>::from_raw(&assets, &newer).is_err());
+        assert!(Migrated::<Bincode<Table>>::from_raw(&assets, &current[..2]).is_err());
+    }
+}
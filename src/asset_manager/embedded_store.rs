@@ -0,0 +1,114 @@
+//! Asset store for assets embedded into the executable.
+
+use fnv::FnvHashMap as HashMap;
+
+use asset_manager::{AssetStore, AssetStoreError};
+
+/// Asset store serving assets which are compiled into the executable, so a
+/// game can be shipped as a single file without an asset directory.
+///
+/// The easiest way to create one is the `embedded_store!` macro, which
+/// embeds files using `include_bytes!`:
+///
+/// ```ignore
+/// #[macro_use]
+/// extern crate amethyst;
+///
+/// let store = embedded_store! {
+///     "cube", "obj" => "../resources/meshes/cube.obj",
+///     "grass", "png" => "../resources/textures/grass.png",
+/// };
+/// assets.register_store(store);
+/// ```
+#[derive(Default)]
+pub struct EmbeddedStore {
+    assets: HashMap<(String, String), &'static [u8]>,
+}
+
+impl EmbeddedStore {
+    /// Creates a new, empty embedded store.
+    pub fn new() -> EmbeddedStore {
+        EmbeddedStore { assets: HashMap::default() }
+    }
+
+    /// Adds an asset with the given name and type to the store.
+    pub fn insert(&mut self, name: &str, asset_type: &str, data: &'static [u8]) {
+        self.assets.insert((name.into(), asset_type.into()), data);
+    }
+
+    fn get(&self, name: &str, asset_type: &str) -> Option<&'static [u8]> {
+        self.assets.get(&(name.into(), asset_type.into())).map(|data| *data)
+    }
+}
+
+impl AssetStore for EmbeddedStore {
+    fn name(&self) -> String {
+        "embedded assets".into()
+    }
+
+    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
+        self.get(name, asset_type).is_some()
+    }
+
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+        match self.get(name, asset_type) {
+            Some(data) => {
+                buf.extend_from_slice(data);
+                Ok(data.len())
+            }
+            None => Err(AssetStoreError::NoSuchAsset),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, AssetStoreError> {
+        let mut names = self.assets
+            .keys()
+            .map(|&(ref name, ref asset_type)| format!("{}.{}", name, asset_type))
+            .filter(|name| name.starts_with(prefix))
+            .collect::<Vec<_>>();
+        names.sort();
+        Ok(names)
+    }
+}
+
+/// Creates an `EmbeddedStore` from a list of `name, type => path` entries.
+/// The files are embedded into the executable using `include_bytes!`, so
+/// paths are relative to the file invoking the macro.
+#[macro_export]
+macro_rules! embedded_store {
+    ($($name:expr, $asset_type:expr => $path:expr),* $(,)*) => {{
+        let mut store = $crate::asset_manager::EmbeddedStore::new();
+        $(store.insert($name, $asset_type, include_bytes!($path));)*
+        store
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EmbeddedStore;
+    use asset_manager::AssetStore;
+
+    #[test]
+    fn load_embedded() {
+        let mut store = EmbeddedStore::new();
+        store.insert("foo", "txt", b"foo");
+
+        let mut buf = Vec::new();
+        assert!(store.has_asset("foo", "txt"));
+        assert!(!store.has_asset("foo", "png"));
+        assert_eq!(store.load_asset("foo", "txt", &mut buf).unwrap(), 3);
+        assert_eq!(buf, b"foo");
+        assert!(store.load_asset("bar", "txt", &mut buf).is_err());
+    }
+
+    #[test]
+    fn list_embedded() {
+        let mut store = EmbeddedStore::new();
+        store.insert("maps/b", "txt", b"b");
+        store.insert("maps/a", "txt", b"a");
+        store.insert("foo", "txt", b"foo");
+
+        assert_eq!(store.list("maps/").unwrap(),
+                   vec!["maps/a.txt".to_string(), "maps/b.txt".to_string()]);
+    }
+}
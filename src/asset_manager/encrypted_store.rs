@@ -0,0 +1,140 @@
+//! Asset store wrapper decrypting the assets of another asset store.
+
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, Nonce, UnboundKey};
+use std::io;
+
+use asset_manager::{AssetStore, AssetStoreError};
+
+/// Length of the nonce prepended to every encrypted asset.
+const NONCE_LEN: usize = 12;
+
+/// Asset store wrapper which decrypts the assets of the store `S` using
+/// AES-256-GCM, before they are handed to the asset loaders.
+///
+/// Every asset has to be stored as a 12 byte nonce, followed by the
+/// ciphertext and the 16 byte authentication tag. No additional data is
+/// authenticated. Never reuse a nonce for two assets encrypted with the same
+/// key.
+pub struct EncryptedStore<S> {
+    inner: S,
+    key: LessSafeKey,
+}
+
+impl<S: AssetStore> EncryptedStore<S> {
+    /// Creates a new encrypted store reading from `inner`, decrypting assets
+    /// with the given 256 bit key.
+    pub fn new(inner: S, key: &[u8; 32]) -> EncryptedStore<S> {
+        let key = UnboundKey::new(&AES_256_GCM, key).expect("AES-256-GCM key has a valid length");
+        EncryptedStore {
+            inner: inner,
+            key: LessSafeKey::new(key),
+        }
+    }
+}
+
+impl<S: AssetStore> AssetStore for EncryptedStore<S> {
+    fn name(&self) -> String {
+        format!("encrypted {}", self.inner.name())
+    }
+
+    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
+        self.inner.has_asset(name, asset_type)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, AssetStoreError> {
+        self.inner.list(prefix)
+    }
+
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+        let mut encrypted = Vec::new();
+        self.inner.load_asset(name, asset_type, &mut encrypted)?;
+
+        if encrypted.len() < NONCE_LEN {
+            return Err(invalid_data("Encrypted asset is too short"));
+        }
+
+        let (nonce, ciphertext) = encrypted.split_at_mut(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| invalid_data("Invalid nonce"))?;
+        let plaintext = self.key
+            .open_in_place(nonce, Aad::empty(), ciphertext)
+            .map_err(|_| invalid_data("Failed to decrypt asset"))?;
+
+        buf.extend_from_slice(plaintext);
+        Ok(plaintext.len())
+    }
+}
+
+fn invalid_data(msg: &str) -> AssetStoreError {
+    AssetStoreError::Io(io::Error::new(io::ErrorKind::InvalidData, msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use ring::aead::{AES_256_GCM, Aad, LessSafeKey, Nonce, UnboundKey};
+
+    use super::EncryptedStore;
+    use asset_manager::{AssetStore, AssetStoreError};
+
+    const KEY: &'static [u8; 32] = b"0123456789abcdef0123456789abcdef";
+    const OTHER_KEY: &'static [u8; 32] = b"fedcba9876543210fedcba9876543210";
+
+    struct SingleAssetStore(Vec<u8>);
+
+    impl AssetStore for SingleAssetStore {
+        fn has_asset(&self, _: &str, _: &str) -> bool {
+            true
+        }
+
+        fn load_asset(&self, _: &str, _: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+            buf.extend_from_slice(&self.0);
+            Ok(self.0.len())
+        }
+    }
+
+    fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> SingleAssetStore {
+        let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key).unwrap());
+        let nonce = [1; 12];
+        let mut ciphertext = plaintext.to_vec();
+        key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce),
+                                      Aad::empty(),
+                                      &mut ciphertext)
+            .unwrap();
+
+        let mut data = nonce.to_vec();
+        data.extend(ciphertext);
+        SingleAssetStore(data)
+    }
+
+    #[test]
+    fn decrypt_assets() {
+        let store = EncryptedStore::new(encrypt(KEY, b"hidden"), KEY);
+
+        let mut buf = Vec::new();
+        assert_eq!(store.load_asset("secret", "txt", &mut buf).unwrap(), 6);
+        assert_eq!(buf, b"hidden");
+    }
+
+    #[test]
+    fn reject_wrong_key() {
+        let store = EncryptedStore::new(encrypt(OTHER_KEY, b"hidden"), KEY);
+
+        let mut buf = Vec::new();
+        assert!(store.load_asset("secret", "txt", &mut buf).is_err());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn reject_tampered_assets() {
+        let mut inner = encrypt(KEY, b"hidden");
+        inner.0[14] ^= 0xff;
+        let store = EncryptedStore::new(inner, KEY);
+
+        let mut buf = Vec::new();
+        assert!(store.load_asset("secret", "txt", &mut buf).is_err());
+        assert!(buf.is_empty());
+
+        let store = EncryptedStore::new(SingleAssetStore(vec![1; 4]), KEY);
+        assert!(store.load_asset("secret", "txt", &mut buf).is_err());
+    }
+}
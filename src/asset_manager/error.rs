@@ -0,0 +1,157 @@
+//! Errors which can occur while loading assets.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Boxed error returned by `AssetLoaderRaw` and `AssetLoader`
+/// implementations.
+pub type LoaderError = Box<Error + Send + Sync>;
+
+/// An error returned by an `AssetStore`.
+#[derive(Debug)]
+pub enum AssetStoreError {
+    /// The store doesn't contain the requested asset.
+    NoSuchAsset,
+    /// The store refused to access the requested asset.
+    PermissionDenied,
+    /// The store didn't respond in time.
+    Timeout,
+    /// The store is currently not available.
+    NotAvailable,
+    /// The asset data is damaged, e.g. it doesn't match its checksum or the
+    /// file it's stored in was cut off.
+    Corrupted,
+    /// An I/O error occurred while reading the asset.
+    Io(io::Error),
+}
+
+impl fmt::Display for AssetStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AssetStoreError::NoSuchAsset => f.write_str("no such asset"),
+            AssetStoreError::PermissionDenied => f.write_str("permission denied"),
+            AssetStoreError::Timeout => f.write_str("timed out"),
+            AssetStoreError::NotAvailable => f.write_str("store not available"),
+            AssetStoreError::Corrupted => f.write_str("corrupted data"),
+            AssetStoreError::Io(ref e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl Error for AssetStoreError {
+    fn description(&self) -> &str {
+        match *self {
+            AssetStoreError::NoSuchAsset => "No such asset",
+            AssetStoreError::PermissionDenied => "Permission denied",
+            AssetStoreError::Timeout => "Timed out",
+            AssetStoreError::NotAvailable => "Store not available",
+            AssetStoreError::Corrupted => "Corrupted data",
+            AssetStoreError::Io(_) => "I/O error",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            AssetStoreError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AssetStoreError {
+    fn from(e: io::Error) -> AssetStoreError {
+        AssetStoreError::Io(e)
+    }
+}
+
+/// The different stages at which loading an asset can fail.
+#[derive(Debug)]
+pub enum AssetErrorKind {
+    /// No loader has been registered for the asset type.
+    UnregisteredAssetType,
+    /// None of the registered asset stores contains the asset.
+    NoSuchAsset,
+    /// No asset store has been registered under the given key, see
+    /// `AssetManager::register_named_store`.
+    UnregisteredStore(String),
+    /// The name is already used by an asset of another type.
+    NameTaken,
+    /// An asset store failed to read the asset.
+    Store {
+        /// Describes the store which failed, see `AssetStore::name`.
+        store: String,
+        /// The error returned by the store.
+        error: AssetStoreError,
+    },
+    /// The raw asset data couldn't be decoded by `AssetLoaderRaw::from_raw`.
+    Decode(LoaderError),
+    /// The decoded data couldn't be turned into the asset by
+    /// `AssetLoader::from_data`, e.g. because uploading it to the GPU failed.
+    Load(LoaderError),
+}
+
+/// An error which occurred while loading an asset.
+#[derive(Debug)]
+pub struct AssetError {
+    /// Name of the asset.
+    pub name: String,
+    /// Type string of the asset (usually its file extension), if it was
+    /// loaded from raw data.
+    pub asset_type: Option<String>,
+    /// What went wrong.
+    pub kind: AssetErrorKind,
+}
+
+impl AssetError {
+    /// Creates a new asset error.
+    pub fn new(name: &str, asset_type: Option<&str>, kind: AssetErrorKind) -> AssetError {
+        AssetError {
+            name: name.into(),
+            asset_type: asset_type.map(Into::into),
+            kind: kind,
+        }
+    }
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.asset_type {
+            Some(ref ty) => write!(f, "Failed to load asset \"{}\" ({}): ", self.name, ty)?,
+            None => write!(f, "Failed to load asset \"{}\": ", self.name)?,
+        }
+
+        match self.kind {
+            AssetErrorKind::UnregisteredAssetType => f.write_str("unregistered asset type"),
+            AssetErrorKind::NoSuchAsset => f.write_str("not found in any asset store"),
+            AssetErrorKind::UnregisteredStore(ref key) => write!(f, "no asset store named \"{}\"", key),
+            AssetErrorKind::NameTaken => f.write_str("name already used by an asset of another type"),
+            AssetErrorKind::Store { ref store, ref error } => write!(f, "{}: {}", store, error),
+            AssetErrorKind::Decode(ref e) => write!(f, "decoding failed: {}", e),
+            AssetErrorKind::Load(ref e) => write!(f, "loading failed: {}", e),
+        }
+    }
+}
+
+impl Error for AssetError {
+    fn description(&self) -> &str {
+        match self.kind {
+            AssetErrorKind::UnregisteredAssetType => "Unregistered asset type",
+            AssetErrorKind::NoSuchAsset => "No such asset",
+            AssetErrorKind::UnregisteredStore(_) => "Unregistered asset store",
+            AssetErrorKind::NameTaken => "Asset name taken",
+            AssetErrorKind::Store { .. } => "Asset store error",
+            AssetErrorKind::Decode(_) => "Failed to decode asset",
+            AssetErrorKind::Load(_) => "Failed to load asset",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match self.kind {
+            AssetErrorKind::Store { ref error, .. } => Some(error),
+            AssetErrorKind::Decode(ref e) |
+            AssetErrorKind::Load(ref e) => Some(&**e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Asset store retrying a primary store before falling back to a secondary
+//! store.
+
+use std::cmp;
+use std::thread;
+use std::time::Duration;
+
+use asset_manager::{AssetStore, AssetStoreError};
+
+/// Longest delay between two attempts, however often the backoff has been
+/// doubled.
+const MAX_DELAY_SECS: u64 = 60;
+
+/// Describes how often and how patiently a failed load is retried.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry. The delay is doubled after every
+    /// further attempt, up to a minute.
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a new retry policy.
+    pub fn new(max_attempts: u32, backoff: Duration) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: max_attempts,
+            backoff: backoff,
+        }
+    }
+
+    /// Returns the delay before the given retry, starting at 1.
+    fn delay(&self, retry: u32) -> Duration {
+        let max = Duration::from_secs(MAX_DELAY_SECS);
+        let factor = 1 << cmp::min(retry.saturating_sub(1), 31);
+        match self.backoff.checked_mul(factor) {
+            Some(delay) => cmp::min(delay, max),
+            None => max,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, waiting 100ms before the first retry.
+    fn default() -> RetryPolicy {
+        RetryPolicy::new(3, Duration::from_millis(100))
+    }
+}
+
+/// Asset store which loads assets from the store `P`, retrying according to
+/// a `RetryPolicy` if it times out or isn't available, and falls back to the
+/// store `S` once all attempts have failed.
+///
+/// This makes networked asset delivery robust against flaky connections,
+/// e.g. by falling back to assets shipped with the game:
+///
+/// ```no_run
+/// use amethyst::asset_manager::{AssetManager, DirectoryStore, FallbackStore};
+///
+/// let mut assets = AssetManager::new();
+/// let remote = DirectoryStore::new("/mnt/asset_server");
+/// assets.register_store(FallbackStore::new(remote, DirectoryStore::new("resources")));
+/// ```
+///
+/// Other errors of the primary store, including `NoSuchAsset`, are returned
+/// as is. Use an `OverlayStore` to fall through on missing assets.
+pub struct FallbackStore<P, S> {
+    primary: P,
+    secondary: S,
+    retry: RetryPolicy,
+}
+
+impl<P: AssetStore, S: AssetStore> FallbackStore<P, S> {
+    /// Creates a new fallback store using the default `RetryPolicy`.
+    pub fn new(primary: P, secondary: S) -> FallbackStore<P, S> {
+        FallbackStore {
+            primary: primary,
+            secondary: secondary,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Sets the retry policy for the primary store, using builder pattern.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> FallbackStore<P, S> {
+        self.retry = retry;
+        self
+    }
+}
+
+impl<P: AssetStore, S: AssetStore> AssetStore for FallbackStore<P, S> {
+    fn name(&self) -> String {
+        format!("{} falling back to {}", self.primary.name(), self.secondary.name())
+    }
+
+    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
+        self.primary.has_asset(name, asset_type) || self.secondary.has_asset(name, asset_type)
+    }
+
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+        let len = buf.len();
+        for attempt in 0..self.retry.max_attempts {
+            if attempt > 0 {
+                thread::sleep(self.retry.delay(attempt));
+            }
+
+            match self.primary.load_asset(name, asset_type, buf) {
+                Err(AssetStoreError::Timeout) |
+                Err(AssetStoreError::NotAvailable) => buf.truncate(len),
+                result => return result,
+            }
+        }
+
+        self.secondary.load_asset(name, asset_type, buf)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, AssetStoreError> {
+        match self.primary.list(prefix) {
+            Err(AssetStoreError::Timeout) |
+            Err(AssetStoreError::NotAvailable) => self.secondary.list(prefix),
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    use super::{FallbackStore, RetryPolicy};
+    use asset_manager::{AssetStore, AssetStoreError};
+
+    /// Store timing out until it has been asked `failures` times.
+    struct FlakyStore {
+        failures: u32,
+        attempts: Cell<u32>,
+    }
+
+    impl AssetStore for FlakyStore {
+        fn has_asset(&self, _: &str, _: &str) -> bool {
+            true
+        }
+
+        fn load_asset(&self, _: &str, _: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+            self.attempts.set(self.attempts.get() + 1);
+            buf.push(0);
+            if self.attempts.get() > self.failures {
+                Ok(1)
+            } else {
+                Err(AssetStoreError::Timeout)
+            }
+        }
+    }
+
+    struct ConstStore(u8);
+
+    impl AssetStore for ConstStore {
+        fn has_asset(&self, _: &str, _: &str) -> bool {
+            true
+        }
+
+        fn load_asset(&self, _: &str, _: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+            buf.push(self.0);
+            Ok(1)
+        }
+    }
+
+    fn flaky(failures: u32) -> FlakyStore {
+        FlakyStore {
+            failures: failures,
+            attempts: Cell::new(0),
+        }
+    }
+
+    #[test]
+    fn retry_then_fall_back() {
+        let retry = RetryPolicy::new(3, Duration::from_millis(0));
+
+        let store = FallbackStore::new(flaky(2), ConstStore(1)).with_retry_policy(retry);
+        let mut buf = Vec::new();
+        store.load_asset("foo", "bar", &mut buf).unwrap();
+        assert_eq!(buf, vec![0]);
+        assert_eq!(store.primary.attempts.get(), 3);
+
+        let store = FallbackStore::new(flaky(3), ConstStore(1)).with_retry_policy(retry);
+        let mut buf = Vec::new();
+        store.load_asset("foo", "bar", &mut buf).unwrap();
+        assert_eq!(buf, vec![1]);
+        assert_eq!(store.primary.attempts.get(), 3);
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let retry = RetryPolicy::new(4, Duration::from_millis(10));
+        assert_eq!(retry.delay(1), Duration::from_millis(10));
+        assert_eq!(retry.delay(2), Duration::from_millis(20));
+        assert_eq!(retry.delay(3), Duration::from_millis(40));
+        assert_eq!(retry.delay(0), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn cap_backoff() {
+        let retry = RetryPolicy::new(100, Duration::from_secs(10));
+        assert_eq!(retry.delay(4), Duration::from_secs(60));
+        assert_eq!(retry.delay(40), Duration::from_secs(60));
+
+        let retry = RetryPolicy::new(100, Duration::from_secs(u64::max_value()));
+        assert_eq!(retry.delay(2), Duration::from_secs(60));
+    }
+}
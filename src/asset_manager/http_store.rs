@@ -0,0 +1,277 @@
+//! Asset store fetching assets over HTTP.
+
+use hyper;
+use hyper::Client;
+use hyper::header::{ETag, EntityTag, HttpDate, IfModifiedSince, IfNoneMatch, LastModified};
+use hyper::status::StatusCode;
+use std::{cmp, fs};
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use asset_manager::{AssetStore, AssetStoreError, check_relative_path};
+
+/// Asset store fetching assets from a web server.
+///
+/// Every fetched asset is written to a local cache directory. On later
+/// loads the cached copy is revalidated with the server using its `ETag` and
+/// `Last-Modified` headers, and only downloaded again if it has changed. If
+/// the server can't be reached, the cached copy is used as is.
+///
+/// Downloads can be throttled with `set_bandwidth_limit`, so streaming
+/// assets in the background doesn't saturate the player's connection.
+pub struct HttpStore {
+    base_url: String,
+    cache_dir: PathBuf,
+    client: Client,
+    bandwidth_limit: Option<u64>,
+}
+
+impl HttpStore {
+    /// Creates a new asset store fetching assets relative to `base_url` and
+    /// caching them in the directory `cache_dir`.
+    pub fn new<P: AsRef<Path>>(base_url: &str, cache_dir: P) -> HttpStore {
+        let mut base_url = base_url.to_string();
+        if !base_url.ends_with('/') {
+            base_url.push('/');
+        }
+
+        HttpStore {
+            base_url: base_url,
+            cache_dir: cache_dir.as_ref().to_path_buf(),
+            client: Client::new(),
+            bandwidth_limit: None,
+        }
+    }
+
+    /// Sets the timeout for reading from and writing to the server.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.client.set_read_timeout(Some(timeout));
+        self.client.set_write_timeout(Some(timeout));
+    }
+
+    /// Limits downloads to the given number of bytes per second, or removes
+    /// the limit if `None` is given.
+    pub fn set_bandwidth_limit(&mut self, bytes_per_second: Option<u64>) {
+        self.bandwidth_limit = bytes_per_second;
+    }
+
+    /// Returns the URL of an asset given the asset's name and type.
+    fn asset_to_url(&self, name: &str, asset_type: &str) -> String {
+        format!("{}{}.{}", self.base_url, name, asset_type)
+    }
+
+    /// Returns the path of the cached copy of an asset given the asset's name
+    /// and type. Names leaving the cache directory are rejected.
+    fn asset_to_cache_path(&self,
+                           name: &str,
+                           asset_type: &str)
+                           -> Result<PathBuf, AssetStoreError> {
+        let file_name = format!("{}.{}", name, asset_type);
+        check_relative_path(&file_name)?;
+        Ok(self.cache_dir.join(file_name))
+    }
+
+    /// Downloads the asset unless the cached copy at `path` is still valid,
+    /// and writes it to the cache.
+    fn fetch(&self, url: &str, path: &Path) -> Result<(), AssetStoreError> {
+        let etag_path = validator_path(path, "etag");
+        let modified_path = validator_path(path, "modified");
+
+        let mut request = self.client.get(url);
+        if path.is_file() {
+            if let Some(etag) = read_header::<EntityTag>(&etag_path) {
+                request = request.header(IfNoneMatch::Items(vec![etag]));
+            }
+            if let Some(date) = read_header::<HttpDate>(&modified_path) {
+                request = request.header(IfModifiedSince(date));
+            }
+        }
+
+        let mut response = request.send().map_err(hyper_to_store_error)?;
+        match response.status {
+            StatusCode::Ok => (),
+            StatusCode::NotModified => return Ok(()),
+            StatusCode::NotFound => return Err(AssetStoreError::NoSuchAsset),
+            StatusCode::Forbidden |
+            StatusCode::Unauthorized => return Err(AssetStoreError::PermissionDenied),
+            StatusCode::RequestTimeout |
+            StatusCode::GatewayTimeout => return Err(AssetStoreError::Timeout),
+            _ => return Err(AssetStoreError::NotAvailable),
+        }
+
+        let mut data = Vec::new();
+        let read = match self.bandwidth_limit {
+            Some(limit) => read_throttled(&mut response, &mut data, limit),
+            None => response.read_to_end(&mut data).map(|_| ()),
+        };
+        read.map_err(io_to_store_error)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::File::create(path)?.write_all(&data)?;
+
+        write_header(&etag_path, response.headers.get::<ETag>().map(|tag| &tag.0))?;
+        write_header(&modified_path, response.headers.get::<LastModified>().map(|date| &date.0))?;
+
+        Ok(())
+    }
+}
+
+impl AssetStore for HttpStore {
+    fn name(&self) -> String {
+        format!("web server \"{}\"", self.base_url)
+    }
+
+    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
+        match self.asset_to_cache_path(name, asset_type) {
+            Ok(ref path) if path.is_file() => return true,
+            Ok(_) => (),
+            Err(_) => return false,
+        }
+
+        let url = self.asset_to_url(name, asset_type);
+        self.client
+            .head(&url)
+            .send()
+            .map(|response| response.status.is_success())
+            .unwrap_or(false)
+    }
+
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+        let path = self.asset_to_cache_path(name, asset_type)?;
+        let url = self.asset_to_url(name, asset_type);
+
+        match self.fetch(&url, &path) {
+            Ok(()) => (),
+            // Fall back to the cached copy if the server can't be reached.
+            Err(AssetStoreError::Timeout) |
+            Err(AssetStoreError::NotAvailable) if path.is_file() => (),
+            Err(e) => return Err(e),
+        }
+
+        let mut file = fs::File::open(path)?;
+        Ok(file.read_to_end(buf)?)
+    }
+}
+
+/// Returns the path of the file storing a cache validator (e.g. the `ETag`)
+/// next to the cached asset at `path`.
+fn validator_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Reads `input` to the end, sleeping as needed to stay below `limit` bytes
+/// per second on average.
+fn read_throttled<R: Read>(input: &mut R, data: &mut Vec<u8>, limit: u64) -> io::Result<()> {
+    let start = Instant::now();
+    let mut chunk = [0; 16 * 1024];
+    let mut total = 0;
+    loop {
+        let len = match input.read(&mut chunk) {
+            Ok(0) => return Ok(()),
+            Ok(len) => len,
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        data.extend_from_slice(&chunk[..len]);
+        total += len as u64;
+
+        let nanos = total * 1_000_000_000 / cmp::max(limit, 1);
+        let expected = Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32);
+        let elapsed = start.elapsed();
+        if expected > elapsed {
+            thread::sleep(expected - elapsed);
+        }
+    }
+}
+
+fn read_header<H: FromStr>(path: &Path) -> Option<H> {
+    let mut value = String::new();
+    match fs::File::open(path) {
+        Ok(mut file) => {
+            if file.read_to_string(&mut value).is_err() {
+                return None;
+            }
+        }
+        Err(_) => return None,
+    }
+    value.trim().parse().ok()
+}
+
+fn write_header<H: ToString>(path: &Path, value: Option<&H>) -> Result<(), AssetStoreError> {
+    match value {
+        Some(value) => fs::File::create(path)?.write_all(value.to_string().as_bytes())?,
+        None => {
+            if path.is_file() {
+                fs::remove_file(path)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn io_to_store_error(e: io::Error) -> AssetStoreError {
+    match e.kind() {
+        ErrorKind::TimedOut | ErrorKind::WouldBlock => AssetStoreError::Timeout,
+        _ => AssetStoreError::Io(e),
+    }
+}
+
+fn hyper_to_store_error(e: hyper::Error) -> AssetStoreError {
+    match e {
+        hyper::Error::Io(e) => {
+            match io_to_store_error(e) {
+                AssetStoreError::Io(_) => AssetStoreError::NotAvailable,
+                e => e,
+            }
+        }
+        _ => AssetStoreError::NotAvailable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::io::Cursor;
+    use std::time::{Duration, Instant};
+
+    use super::{HttpStore, read_throttled};
+    use asset_manager::{AssetStore, AssetStoreError};
+
+    #[test]
+    fn throttle_downloads() {
+        let mut data = Vec::new();
+        let start = Instant::now();
+        read_throttled(&mut Cursor::new(vec![7; 1000]), &mut data, 20_000).unwrap();
+
+        assert_eq!(data, vec![7; 1000]);
+        assert!(start.elapsed() >= Duration::from_millis(45));
+    }
+
+    #[test]
+    fn reject_paths_outside_cache() {
+        // Nothing listens on port 1, but the names are rejected before any
+        // request is sent anyway.
+        let cache_dir = env::temp_dir().join("amethyst_http_cache");
+        let store = HttpStore::new("http://127.0.0.1:1/", cache_dir);
+        let mut buf = Vec::new();
+        match store.load_asset("../x", "png", &mut buf) {
+            Err(AssetStoreError::PermissionDenied) => (),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        match store.load_asset("/etc/passwd", "png", &mut buf) {
+            Err(AssetStoreError::PermissionDenied) => (),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert!(!store.has_asset("../x", "png"));
+        assert!(buf.is_empty());
+    }
+}
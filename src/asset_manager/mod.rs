@@ -1,4 +1,54 @@
 //! Asset manager used to load assets (like `Mesh`es and `Texture`s).
 
+#[cfg(target_os="android")]
+mod apk_store;
 mod asset_manager;
+mod compressed_store;
+#[cfg(any(feature="bincode", feature="ron", feature="serde_json", feature="toml"))]
+mod data_formats;
+mod embedded_store;
+#[cfg(feature="ring")]
+mod encrypted_store;
+mod error;
+mod fallback_store;
+#[cfg(feature="hyper")]
+mod http_store;
+mod overlay_store;
+pub mod pack;
+mod pack_store;
+mod prefab;
+mod preload;
+mod progress;
+mod stats;
+#[cfg(feature="zip")]
+mod zip_store;
+
+#[cfg(target_os="android")]
+pub use self::apk_store::ApkStore;
 pub use self::asset_manager::*;
+pub use self::compressed_store::{CompressedStore, Compression};
+#[cfg(feature="bincode")]
+pub use self::data_formats::Bincode;
+#[cfg(any(feature="bincode", feature="ron", feature="serde_json", feature="toml"))]
+pub use self::data_formats::{DataFormat, FirstVersion, Migrated, Versioned};
+#[cfg(feature="serde_json")]
+pub use self::data_formats::Json;
+#[cfg(feature="ron")]
+pub use self::data_formats::Ron;
+#[cfg(feature="toml")]
+pub use self::data_formats::Toml;
+pub use self::embedded_store::EmbeddedStore;
+#[cfg(feature="ring")]
+pub use self::encrypted_store::EncryptedStore;
+pub use self::error::{AssetError, AssetErrorKind, AssetStoreError, LoaderError};
+pub use self::fallback_store::{FallbackStore, RetryPolicy};
+#[cfg(feature="hyper")]
+pub use self::http_store::HttpStore;
+pub use self::overlay_store::OverlayStore;
+pub use self::pack_store::PackStore;
+pub use self::prefab::{Prefab, PrefabEntity, PrefabOverride, PrefabRenderable};
+pub use self::preload::PreloadList;
+pub use self::progress::ProgressCounter;
+pub use self::stats::{AssetInfo, AssetStats, AssetTypeStats};
+#[cfg(feature="zip")]
+pub use self::zip_store::ZipStore;
@@ -0,0 +1,165 @@
+//! Asset store layering several asset stores on top of each other.
+
+use asset_manager::{AssetStore, AssetStoreError};
+
+/// Asset store composed of several asset stores in priority order.
+///
+/// Assets are looked up in each store in turn, falling through to the next
+/// store if an asset can't be found. Putting a `mods/` directory in front of
+/// the game's `base/` directory lets mods replace any asset without changes
+/// to the game code.
+///
+/// # Example
+///
+/// ```no_run
+/// use amethyst::asset_manager::{AssetManager, DirectoryStore, OverlayStore};
+///
+/// let mut assets = AssetManager::new();
+/// assets.register_store(OverlayStore::new()
+///     .with(DirectoryStore::new("mods"))
+///     .with(DirectoryStore::new("base")));
+/// ```
+#[derive(Default)]
+pub struct OverlayStore {
+    stores: Vec<Box<AssetStore>>,
+}
+
+impl OverlayStore {
+    /// Creates a new overlay store without any stores.
+    pub fn new() -> OverlayStore {
+        OverlayStore { stores: Vec::new() }
+    }
+
+    /// Adds a store with a lower priority than all stores added before,
+    /// using builder pattern.
+    pub fn with<T: AssetStore + 'static>(mut self, store: T) -> OverlayStore {
+        self.push(store);
+        self
+    }
+
+    /// Adds a store with a lower priority than all stores added before.
+    pub fn push<T: AssetStore + 'static>(&mut self, store: T) {
+        self.stores.push(Box::new(store));
+    }
+}
+
+impl AssetStore for OverlayStore {
+    fn name(&self) -> String {
+        let names = self.stores.iter().map(|store| store.name()).collect::<Vec<_>>();
+        format!("overlay of [{}]", names.join(", "))
+    }
+
+    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
+        self.stores.iter().any(|store| store.has_asset(name, asset_type))
+    }
+
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+        for store in &self.stores {
+            match store.load_asset(name, asset_type, buf) {
+                Err(AssetStoreError::NoSuchAsset) => continue,
+                result => return result,
+            }
+        }
+
+        Err(AssetStoreError::NoSuchAsset)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, AssetStoreError> {
+        let mut names = Vec::new();
+        for store in &self.stores {
+            match store.list(prefix) {
+                Ok(list) => names.extend(list),
+                Err(AssetStoreError::NotAvailable) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OverlayStore;
+    use asset_manager::{AssetStore, AssetStoreError};
+
+    struct SingleAssetStore(&'static str, u8);
+
+    impl AssetStore for SingleAssetStore {
+        fn has_asset(&self, name: &str, _: &str) -> bool {
+            name == self.0
+        }
+
+        fn load_asset(&self, name: &str, _: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+            if name == self.0 {
+                buf.push(self.1);
+                Ok(1)
+            } else {
+                Err(AssetStoreError::NoSuchAsset)
+            }
+        }
+    }
+
+    #[test]
+    fn priority_order() {
+        let store = OverlayStore::new()
+            .with(SingleAssetStore("a", 1))
+            .with(SingleAssetStore("a", 2))
+            .with(SingleAssetStore("b", 3));
+
+        let mut buf = Vec::new();
+        store.load_asset("a", "foo", &mut buf).unwrap();
+        assert_eq!(buf, vec![1]);
+
+        buf.clear();
+        store.load_asset("b", "foo", &mut buf).unwrap();
+        assert_eq!(buf, vec![3]);
+
+        assert!(store.has_asset("b", "foo"));
+        assert!(!store.has_asset("c", "foo"));
+        match store.load_asset("c", "foo", &mut buf) {
+            Err(AssetStoreError::NoSuchAsset) => (),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn overlay_directories() {
+        use asset_manager::DirectoryStore;
+        use std::env;
+        use std::fs::{self, File};
+        use std::io::Write;
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+        let root = env::temp_dir().join(format!("amethyst_overlay_directories_{}", nanos));
+        let mods = root.join("mods");
+        let base = root.join("base");
+        fs::create_dir_all(&mods).unwrap();
+        fs::create_dir_all(&base).unwrap();
+        File::create(mods.join("a.foo")).unwrap().write_all(b"mod").unwrap();
+        File::create(base.join("a.foo")).unwrap().write_all(b"base").unwrap();
+        File::create(base.join("b.foo")).unwrap().write_all(b"base").unwrap();
+
+        let store = OverlayStore::new()
+            .with(DirectoryStore::new(&mods))
+            .with(DirectoryStore::new(&base));
+
+        let mut buf = Vec::new();
+        store.load_asset("a", "foo", &mut buf).unwrap();
+        assert_eq!(buf, b"mod");
+
+        buf.clear();
+        store.load_asset("b", "foo", &mut buf).unwrap();
+        assert_eq!(buf, b"base");
+
+        let missing = store.load_asset("c", "foo", &mut buf);
+        fs::remove_dir_all(&root).unwrap();
+        match missing {
+            Err(AssetStoreError::NoSuchAsset) => (),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Packing assets into a single pack file for release builds, read at
+//! runtime by a `PackStore`.
+//!
+//! A pack file starts with the magic bytes `AMPK`, a format version and the
+//! manifest: the number of entries, followed by the name, offset, length, and
+//! checksum of every entry. The asset data follows the manifest. All integers
+//! are little endian. Checksums are 64 bit FNV-1a hashes of the asset data as
+//! stored in the pack; version 1 pack files have no checksums.
+
+use fnv::FnvHasher;
+use std::hash::Hasher;
+use std::io::{self, Read, Write};
+
+use asset_manager::{AssetStore, AssetStoreError, Compression, split_extension};
+
+/// Magic bytes starting a pack file.
+pub const MAGIC: &'static [u8; 4] = b"AMPK";
+
+/// Version of the pack file format written by `write_pack`.
+pub const VERSION: u32 = 2;
+
+/// An entry of the manifest of a pack file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackEntry {
+    /// File name of the asset (`name.type`).
+    pub name: String,
+    /// Offset of the asset data from the start of the pack file.
+    pub offset: u64,
+    /// Length of the asset data.
+    pub len: u64,
+    /// Checksum of the asset data, see `checksum`. `None` for pack files
+    /// written before checksums were added.
+    pub checksum: Option<u64>,
+}
+
+/// Computes the checksum of asset data stored in a pack file.
+pub fn checksum(data: &[u8]) -> u64 {
+    let mut hasher = FnvHasher::default();
+    hasher.write(data);
+    hasher.finish()
+}
+
+/// Writes all assets of `store` to `out` as a pack file, and returns its
+/// manifest. The store has to support `AssetStore::list`. Files without an
+/// extension aren't assets and are skipped.
+///
+/// If `compression` is given, every asset is compressed and prefixed with
+/// `Compression::header`, so the pack file has to be read through a
+/// `CompressedStore`.
+pub fn write_pack<W: Write>(store: &AssetStore,
+                            compression: Option<Compression>,
+                            mut out: W)
+                            -> Result<Vec<PackEntry>, AssetStoreError> {
+    let mut assets = Vec::new();
+    for file in store.list("")? {
+        let (name, asset_type) = match split_extension(&file) {
+            Some(split) => split,
+            None => continue,
+        };
+
+        let mut data = Vec::new();
+        store.load_asset(name, asset_type, &mut data)?;
+        if let Some(method) = compression {
+            let mut compressed = method.header().to_vec();
+            compressed.extend(method.compress(&data)?);
+            data = compressed;
+        }
+        assets.push((file.clone(), data));
+    }
+
+    // Header, entry count, and per entry the name length, offset, length, and
+    // checksum.
+    let manifest_len = assets.iter().fold(12, |len, &(ref name, _)| len + 2 + name.len() + 24);
+    let mut offset = manifest_len as u64;
+    let mut entries = Vec::with_capacity(assets.len());
+    for &(ref name, ref data) in &assets {
+        entries.push(PackEntry {
+            name: name.clone(),
+            offset: offset,
+            len: data.len() as u64,
+            checksum: Some(checksum(data)),
+        });
+        offset += data.len() as u64;
+    }
+
+    out.write_all(MAGIC)?;
+    out.write_all(&u32_to_bytes(VERSION))?;
+    out.write_all(&u32_to_bytes(entries.len() as u32))?;
+    for entry in &entries {
+        if entry.name.len() > u16::max_value() as usize {
+            let msg = format!("Asset name too long: {}", entry.name);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg).into());
+        }
+        out.write_all(&u16_to_bytes(entry.name.len() as u16))?;
+        out.write_all(entry.name.as_bytes())?;
+        out.write_all(&u64_to_bytes(entry.offset))?;
+        out.write_all(&u64_to_bytes(entry.len))?;
+        out.write_all(&u64_to_bytes(entry.checksum.unwrap_or(0)))?;
+    }
+    for &(_, ref data) in &assets {
+        out.write_all(data)?;
+    }
+
+    Ok(entries)
+}
+
+/// Reads the manifest of a pack file.
+pub fn read_manifest<R: Read>(mut input: R) -> io::Result<Vec<PackEntry>> {
+    let mut magic = [0; 4];
+    input.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a pack file"));
+    }
+
+    let version = read_u32(&mut input)?;
+    if version == 0 || version > VERSION {
+        let msg = format!("Unsupported pack file version {}", version);
+        return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+    }
+
+    let count = read_u32(&mut input)?;
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let mut len = [0; 2];
+        input.read_exact(&mut len)?;
+        let mut name = vec![0; (len[0] as usize) | (len[1] as usize) << 8];
+        input.read_exact(&mut name)?;
+
+        let name = String::from_utf8(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let offset = read_u64(&mut input)?;
+        let len = read_u64(&mut input)?;
+        let checksum = if version >= 2 {
+            Some(read_u64(&mut input)?)
+        } else {
+            None
+        };
+
+        entries.push(PackEntry {
+            name: name,
+            offset: offset,
+            len: len,
+            checksum: checksum,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn u16_to_bytes(n: u16) -> [u8; 2] {
+    [n as u8, (n >> 8) as u8]
+}
+
+fn u32_to_bytes(n: u32) -> [u8; 4] {
+    [n as u8, (n >> 8) as u8, (n >> 16) as u8, (n >> 24) as u8]
+}
+
+fn u64_to_bytes(n: u64) -> [u8; 8] {
+    let (low, high) = (u32_to_bytes(n as u32), u32_to_bytes((n >> 32) as u32));
+    [low[0], low[1], low[2], low[3], high[0], high[1], high[2], high[3]]
+}
+
+fn read_u32<R: Read>(input: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    input.read_exact(&mut bytes)?;
+    Ok(bytes.iter().rev().fold(0, |n, &byte| n << 8 | byte as u32))
+}
+
+fn read_u64<R: Read>(input: &mut R) -> io::Result<u64> {
+    let low = read_u32(input)? as u64;
+    let high = read_u32(input)? as u64;
+    Ok(high << 32 | low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checksum, read_manifest, write_pack};
+    use asset_manager::{DirectoryStore, EmbeddedStore};
+
+    #[test]
+    fn manifest_round_trip() {
+        let mut store = EmbeddedStore::new();
+        store.insert("textures/grass", "png", b"grass");
+        store.insert("meshes/cube", "obj", b"cube");
+
+        let mut pack = Vec::new();
+        let entries = write_pack(&store, None, &mut pack).unwrap();
+        assert_eq!(read_manifest(&pack[..]).unwrap(), entries);
+
+        let cube = &entries[0];
+        assert_eq!(cube.name, "meshes/cube.obj");
+        let start = cube.offset as usize;
+        assert_eq!(&pack[start..start + cube.len as usize], b"cube");
+        assert_eq!(cube.checksum, Some(checksum(b"cube")));
+    }
+
+    #[test]
+    fn skip_files_without_extension() {
+        use std::env;
+        use std::fs::{self, File};
+        use std::io::Write;
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+        let root = env::temp_dir().join(format!("amethyst_pack_extensions_{}", nanos));
+        fs::create_dir_all(root.join("levels.v2")).unwrap();
+        File::create(root.join("levels.v2/readme")).unwrap().write_all(b"notes").unwrap();
+        File::create(root.join("levels.v2/intro.tmx")).unwrap().write_all(b"map").unwrap();
+
+        let mut pack = Vec::new();
+        let entries = write_pack(&DirectoryStore::new(&root), None, &mut pack).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "levels.v2/intro.tmx");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,184 @@
+//! Asset store reading from a pack file.
+
+use fnv::FnvHashMap as HashMap;
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use asset_manager::{AssetStore, AssetStoreError};
+use asset_manager::pack::{PackEntry, checksum, read_manifest};
+
+/// Asset store representing a pack file written by `pack::write_pack`.
+///
+/// The manifest is read once on creation and kept in memory for lookups.
+/// Entries reaching past the end of the file are rejected with
+/// `AssetStoreError::Corrupted` on creation. Loaded assets are checked
+/// against the checksums in the manifest, and `AssetStoreError::Corrupted`
+/// is returned if they don't match. Wrap the
+/// store in a `CompressedStore` if the pack was written with compression.
+pub struct PackStore {
+    path: PathBuf,
+    file: RefCell<File>,
+    entries: HashMap<String, PackEntry>,
+}
+
+impl PackStore {
+    /// Opens the pack file at the given path.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<PackStore, AssetStoreError> {
+        let mut file = File::open(path.as_ref())?;
+        let manifest = read_manifest(BufReader::new(&mut file))?;
+        let file_len = file.metadata()?.len();
+        for entry in &manifest {
+            match entry.offset.checked_add(entry.len) {
+                Some(end) if end <= file_len => (),
+                _ => return Err(AssetStoreError::Corrupted),
+            }
+        }
+
+        let entries = manifest.into_iter()
+            .map(|entry| (entry.name.clone(), entry))
+            .collect();
+
+        Ok(PackStore {
+            path: path.as_ref().to_path_buf(),
+            file: RefCell::new(file),
+            entries: entries,
+        })
+    }
+}
+
+impl AssetStore for PackStore {
+    fn name(&self) -> String {
+        format!("pack file \"{}\"", self.path.display())
+    }
+
+    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
+        self.entries.contains_key(&format!("{}.{}", name, asset_type))
+    }
+
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+        let entry = match self.entries.get(&format!("{}.{}", name, asset_type)) {
+            Some(entry) => entry,
+            None => return Err(AssetStoreError::NoSuchAsset),
+        };
+
+        let mut file = self.file.borrow_mut();
+        file.seek(SeekFrom::Start(entry.offset))?;
+        let start = buf.len();
+        let read = match (&mut *file).take(entry.len).read_to_end(buf) {
+            Ok(read) => read,
+            Err(e) => {
+                buf.truncate(start);
+                return Err(e.into());
+            }
+        };
+        // The file may have been cut off since the manifest was checked.
+        if read as u64 != entry.len {
+            buf.truncate(start);
+            return Err(AssetStoreError::Corrupted);
+        }
+
+        if let Some(expected) = entry.checksum {
+            if checksum(&buf[start..]) != expected {
+                buf.truncate(start);
+                return Err(AssetStoreError::Corrupted);
+            }
+        }
+
+        Ok(entry.len as usize)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, AssetStoreError> {
+        let mut names = self.entries
+            .keys()
+            .filter(|name| name.starts_with(prefix))
+            .cloned()
+            .collect::<Vec<_>>();
+        names.sort();
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::PackStore;
+    use asset_manager::{AssetStore, AssetStoreError, EmbeddedStore};
+    use asset_manager::pack::write_pack;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+        env::temp_dir().join(format!("amethyst_{}_{}.pack", name, nanos))
+    }
+
+    #[test]
+    fn read_pack() {
+        let mut store = EmbeddedStore::new();
+        store.insert("textures/grass", "png", b"grass");
+        store.insert("meshes/cube", "obj", b"cube");
+
+        let path = temp_path("read_pack");
+        write_pack(&store, None, File::create(&path).unwrap()).unwrap();
+
+        let pack = PackStore::new(&path).unwrap();
+        let mut buf = Vec::new();
+        assert!(pack.has_asset("textures/grass", "png"));
+        assert_eq!(pack.load_asset("textures/grass", "png", &mut buf).unwrap(), 5);
+        assert_eq!(buf, b"grass");
+        assert!(pack.load_asset("textures/dirt", "png", &mut buf).is_err());
+        assert_eq!(pack.list("meshes/").unwrap(), vec!["meshes/cube.obj".to_string()]);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn detect_corruption() {
+        let mut store = EmbeddedStore::new();
+        store.insert("textures/grass", "png", b"grass");
+
+        let mut pack = Vec::new();
+        write_pack(&store, None, &mut pack).unwrap();
+        let last = pack.len() - 1;
+        pack[last] ^= 0xff;
+
+        let path = temp_path("detect_corruption");
+        File::create(&path).unwrap().write_all(&pack).unwrap();
+
+        let pack = PackStore::new(&path).unwrap();
+        let mut buf = Vec::new();
+        match pack.load_asset("textures/grass", "png", &mut buf) {
+            Err(AssetStoreError::Corrupted) => (),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert!(buf.is_empty());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reject_truncated_pack() {
+        let mut store = EmbeddedStore::new();
+        store.insert("textures/grass", "png", b"grass");
+
+        let mut pack = Vec::new();
+        write_pack(&store, None, &mut pack).unwrap();
+        let len = pack.len() - 2;
+
+        let path = temp_path("truncated");
+        File::create(&path).unwrap().write_all(&pack[..len]).unwrap();
+
+        match PackStore::new(&path) {
+            Err(AssetStoreError::Corrupted) => (),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+            Ok(_) => panic!("Truncated pack was accepted"),
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+}
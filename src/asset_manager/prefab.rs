@@ -0,0 +1,360 @@
+//! Prefabs describing entity trees in asset files.
+
+use std::path::Path;
+use std::str;
+
+use asset_manager::{AssetLoader, AssetLoaderRaw, AssetManager, AssetReadStorage, Assets, LoaderError};
+use Float;
+use config::Element;
+use ecs::{Entity, World};
+use ecs::components::{Child, LocalTransform, Renderable, Transform};
+
+/// Maximum depth of nested prefabs, guarding against prefabs including
+/// themselves.
+const MAX_DEPTH: usize = 16;
+
+config! {
+    /// Renderable of a prefab entity, see `AssetManager::create_renderable`.
+    struct PrefabRenderable {
+        /// Name of the mesh asset.
+        pub mesh: String = String::new(),
+        /// Name of the ambient texture asset.
+        pub ambient: String = String::new(),
+        /// Name of the diffuse texture asset.
+        pub diffuse: String = String::new(),
+        /// Name of the specular texture asset.
+        pub specular: String = String::new(),
+        /// Specular exponent of the material.
+        pub specular_exponent: f32 = 1.0,
+    }
+}
+
+config! {
+    /// Overrides fields of an entity of a nested prefab, see
+    /// `PrefabEntity::prefab`. Fields which are `None` keep the nested
+    /// prefab's value.
+    struct PrefabOverride {
+        /// Index of the overridden entity in the nested prefab's entities.
+        pub entity: u32 = 0,
+        /// Overrides the translation.
+        pub translation: Option<[Float; 3]> = None,
+        /// Overrides the rotation.
+        pub rotation: Option<[Float; 4]> = None,
+        /// Overrides the scale.
+        pub scale: Option<[Float; 3]> = None,
+        /// Overrides the renderable, e.g. to change the material.
+        pub renderable: Option<PrefabRenderable> = None,
+    }
+}
+
+config! {
+    /// A single entity of a `Prefab`.
+    struct PrefabEntity {
+        /// Index of the parent entity in `Prefab::entities`, which has to come
+        /// before this entity. Entities without a parent are attached to the
+        /// prefab's root entity.
+        pub parent: Option<u32> = None,
+        /// Translation relative to the parent.
+        pub translation: [Float; 3] = [0.0, 0.0, 0.0],
+        /// Rotation quaternion [w (scalar), x, y, z] relative to the parent.
+        pub rotation: [Float; 4] = [1.0, 0.0, 0.0, 0.0],
+        /// Scale relative to the parent.
+        pub scale: [Float; 3] = [1.0, 1.0, 1.0],
+        /// Renderable created from loaded assets, if any.
+        pub renderable: Option<PrefabRenderable> = None,
+        /// Name of another prefab whose entities are attached to this entity,
+        /// see `AssetManager::load_prefab`.
+        pub prefab: Option<String> = None,
+        /// Overrides applied to the entities of the nested `prefab`.
+        pub overrides: Vec<PrefabOverride> = Vec::new(),
+    }
+}
+
+config! {
+    /// A tree of entities described in a YAML `.prefab` file, which can be
+    /// spawned any number of times with `AssetManager::instantiate_prefab`.
+    ///
+    /// ```yaml
+    /// entities:
+    ///   - renderable:
+    ///       mesh: "teapot"
+    ///       ambient: "red"
+    ///       diffuse: "red"
+    ///       specular: "white"
+    ///       specular_exponent: 10.0
+    ///   - parent: 0
+    ///     translation: [0.0, 1.0, 0.0]
+    ///     renderable:
+    ///       mesh: "lid"
+    ///       ambient: "red"
+    ///       diffuse: "red"
+    ///       specular: "white"
+    /// ```
+    struct Prefab {
+        /// The entities of the prefab, parents before their children.
+        pub entities: Vec<PrefabEntity> = Vec::new(),
+    }
+}
+
+/// An entity of a prefab with all nested prefabs expanded.
+struct FlatEntity {
+    /// Index of the parent in the flattened entities, `None` for the root.
+    parent: Option<usize>,
+    local: LocalTransform,
+    renderable: Option<Renderable>,
+}
+
+impl Prefab {
+    /// Creates the entities of the prefab and all nested prefabs in `world`,
+    /// taking meshes, textures, and nested prefabs from `assets`. Returns the
+    /// root entity all entities are attached to.
+    ///
+    /// Returns `None` without creating any entities if an asset is missing,
+    /// an entity's parent doesn't come before it, or an override refers to a
+    /// missing entity.
+    pub fn instantiate(&self, world: &mut World, assets: &AssetManager) -> Option<Entity> {
+        let flat = match self.flatten(assets, 0) {
+            Some(flat) => flat,
+            None => return None,
+        };
+
+        let root = world.create_now()
+            .with(LocalTransform::default())
+            .with(Transform::default())
+            .build();
+
+        let mut created = Vec::with_capacity(flat.len());
+        for entity in flat {
+            let parent = match entity.parent {
+                Some(parent) => created[parent],
+                None => root,
+            };
+
+            let builder = world.create_now()
+                .with(entity.local)
+                .with(Transform::default())
+                .with(Child::new(parent));
+            let builder = match entity.renderable {
+                Some(renderable) => builder.with(renderable),
+                None => builder,
+            };
+            created.push(builder.build());
+        }
+
+        Some(root)
+    }
+
+    /// Applies overrides to a copy of this prefab.
+    fn with_overrides(&self, overrides: &[PrefabOverride]) -> Option<Prefab> {
+        let mut prefab = self.clone();
+        for o in overrides {
+            let entity = match prefab.entities.get_mut(o.entity as usize) {
+                Some(entity) => entity,
+                None => return None,
+            };
+
+            if let Some(translation) = o.translation {
+                entity.translation = translation;
+            }
+            if let Some(rotation) = o.rotation {
+                entity.rotation = rotation;
+            }
+            if let Some(scale) = o.scale {
+                entity.scale = scale;
+            }
+            if o.renderable.is_some() {
+                entity.renderable = o.renderable.clone();
+            }
+        }
+
+        Some(prefab)
+    }
+
+    /// Expands nested prefabs into a single list of entities, parents before
+    /// their children.
+    fn flatten(&self, assets: &AssetManager, depth: usize) -> Option<Vec<FlatEntity>> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        let mut flat = Vec::with_capacity(self.entities.len());
+        let mut indices = Vec::with_capacity(self.entities.len());
+        for (index, entity) in self.entities.iter().enumerate() {
+            let parent = match entity.parent {
+                Some(parent) if (parent as usize) < index => Some(indices[parent as usize]),
+                Some(_) => return None,
+                None => None,
+            };
+
+            let renderable = match entity.renderable {
+                Some(ref r) => {
+                    match assets.create_renderable(&r.mesh,
+                                                   &r.ambient,
+                                                   &r.diffuse,
+                                                   &r.specular,
+                                                   r.specular_exponent) {
+                        Some(renderable) => Some(renderable),
+                        None => return None,
+                    }
+                }
+                None => None,
+            };
+
+            let mut local = LocalTransform::default();
+            local.translation = entity.translation;
+            local.rotation = entity.rotation;
+            local.scale = entity.scale;
+
+            let own = flat.len();
+            indices.push(own);
+            flat.push(FlatEntity {
+                parent: parent,
+                local: local,
+                renderable: renderable,
+            });
+
+            if let Some(ref name) = entity.prefab {
+                let nested = match loaded_prefab(assets, name).and_then(|p| p.with_overrides(&entity.overrides)) {
+                    Some(nested) => nested,
+                    None => return None,
+                };
+                let nested = match nested.flatten(assets, depth + 1) {
+                    Some(nested) => nested,
+                    None => return None,
+                };
+
+                for mut child in nested {
+                    child.parent = Some(match child.parent {
+                        Some(parent) => own + 1 + parent,
+                        None => own,
+                    });
+                    flat.push(child);
+                }
+            }
+        }
+
+        Some(flat)
+    }
+}
+
+/// Returns a copy of the loaded prefab with the given name.
+fn loaded_prefab(assets: &AssetManager, name: &str) -> Option<Prefab> {
+    assets.id_from_name(name).and_then(|id| assets.read_assets::<Prefab>().read(id).cloned())
+}
+
+impl AssetLoaderRaw for Prefab {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<Prefab, LoaderError> {
+        let source = str::from_utf8(data)?;
+        Prefab::from_string(source).map_err(|e| e.to_string().into())
+    }
+}
+
+impl AssetLoader<Prefab> for Prefab {
+    fn from_data(_: &mut Assets, prefab: Prefab) -> Result<Prefab, LoaderError> {
+        Ok(prefab)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Prefab;
+    use asset_manager::AssetManager;
+    use ecs::{Join, World};
+    use ecs::components::{Child, LocalTransform, Transform};
+
+    #[test]
+    fn instantiate_tree() {
+        let prefab = Prefab::from_string("
+entities:
+  - translation: [0.0, 1.0, 0.0]
+  - parent: 0
+    scale: [2.0, 2.0, 2.0]
+")
+            .unwrap();
+
+        let mut world = World::new();
+        world.register::<Child>();
+        world.register::<LocalTransform>();
+        world.register::<Transform>();
+
+        let assets = AssetManager::new();
+        let root = prefab.instantiate(&mut world, &assets).unwrap();
+
+        let entities = world.entities();
+        let children = world.read::<Child>();
+        let locals = world.read::<LocalTransform>();
+        let mut parents = Vec::new();
+        for (entity, child) in (&entities, &children).iter() {
+            parents.push((entity, child.parent()));
+        }
+
+        assert_eq!(parents.len(), 2);
+        let first = parents.iter().find(|&&(_, parent)| parent == root).unwrap().0;
+        let second = parents.iter().find(|&&(_, parent)| parent == first).unwrap().0;
+        assert_eq!(locals.get(first).unwrap().translation, [0.0, 1.0, 0.0]);
+        assert_eq!(locals.get(second).unwrap().scale, [2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn missing_assets() {
+        let prefab = Prefab::from_string("
+entities:
+  - renderable:
+      mesh: \"missing\"
+")
+            .unwrap();
+
+        let mut world = World::new();
+        world.register::<Child>();
+        world.register::<LocalTransform>();
+        world.register::<Transform>();
+
+        let assets = AssetManager::new();
+        assert_eq!(prefab.instantiate(&mut world, &assets), None);
+    }
+
+    #[test]
+    fn nested_prefab_overrides() {
+        let mut assets = AssetManager::new();
+        assets.load_asset_from_raw::<Prefab>("wheel", "prefab", b"
+entities:
+  - translation: [1.0, 0.0, 0.0]
+  - parent: 0
+    translation: [0.0, 0.0, 1.0]
+")
+            .unwrap();
+        assets.load_asset_from_raw::<Prefab>("cart", "prefab", b"
+entities:
+  - prefab: \"wheel\"
+  - prefab: \"wheel\"
+    overrides:
+      - entity: 1
+        translation: [0.0, 0.0, -1.0]
+")
+            .unwrap();
+
+        let mut world = World::new();
+        world.register::<Child>();
+        world.register::<LocalTransform>();
+        world.register::<Transform>();
+        assets.instantiate_prefab("cart", &mut world).unwrap();
+
+        let entities = world.entities();
+        let children = world.read::<Child>();
+        let locals = world.read::<LocalTransform>();
+        let mut translations = (&entities, &children, &locals)
+            .iter()
+            .map(|(_, _, local)| local.translation)
+            .collect::<Vec<_>>();
+        translations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        // Both cart entities sit at the origin, the second wheel is overridden.
+        assert_eq!(translations,
+                   vec![[0.0, 0.0, -1.0],
+                        [0.0, 0.0, 0.0],
+                        [0.0, 0.0, 0.0],
+                        [0.0, 0.0, 1.0],
+                        [1.0, 0.0, 0.0],
+                        [1.0, 0.0, 0.0]]);
+    }
+}
@@ -0,0 +1,16 @@
+//! Lists of assets to load up front, e.g. when entering a state.
+
+config! {
+    /// A list of assets described in a YAML `.preload` file, which are loaded
+    /// all at once with `AssetManager::preload`.
+    ///
+    /// ```yaml
+    /// assets:
+    ///   - ["meshes/cube", "obj"]
+    ///   - ["textures/crate", "png"]
+    /// ```
+    struct PreloadList {
+        /// Name and type of every asset to load.
+        pub assets: Vec<(String, String)> = Vec::new(),
+    }
+}
@@ -0,0 +1,104 @@
+//! Tracking of batched asset loads, e.g. for loading screens.
+
+/// Counts how many assets of a batch are still loading, have finished
+/// loading, or have failed to load.
+///
+/// Pass it to `AssetManager::load_assets`, or to
+/// `AssetManager::load_asset_with_progress` for every asset of the batch. If
+/// the batch is loaded over several frames, announce its size up front with
+/// `add_assets()` so `num_loading()` reports the remaining assets correctly.
+/// Announced assets are not counted again when they are loaded.
+#[derive(Clone, Debug, Default)]
+pub struct ProgressCounter {
+    num_assets: usize,
+    num_finished: usize,
+    num_failed: usize,
+}
+
+impl ProgressCounter {
+    /// Creates a new, empty progress counter.
+    pub fn new() -> ProgressCounter {
+        ProgressCounter::default()
+    }
+
+    /// Announces `num` additional assets which will be loaded using this
+    /// counter.
+    pub fn add_assets(&mut self, num: usize) {
+        self.num_assets += num;
+    }
+
+    /// Returns the total number of assets tracked by this counter.
+    pub fn num_assets(&self) -> usize {
+        self.num_assets
+    }
+
+    /// Returns the number of assets which have not been loaded yet.
+    pub fn num_loading(&self) -> usize {
+        self.num_assets - self.num_finished - self.num_failed
+    }
+
+    /// Returns the number of assets which have been loaded successfully.
+    pub fn num_finished(&self) -> usize {
+        self.num_finished
+    }
+
+    /// Returns the number of assets which failed to load.
+    pub fn num_failed(&self) -> usize {
+        self.num_failed
+    }
+
+    /// Returns whether all assets of the batch have either finished or failed
+    /// loading.
+    pub fn is_complete(&self) -> bool {
+        self.num_loading() == 0
+    }
+
+    /// Records the outcome of a single asset load. This is done
+    /// automatically by `AssetManager::load_asset_with_progress`.
+    pub fn track(&mut self, success: bool) {
+        // Loads which weren't announced through `add_assets()` are counted
+        // as they come in.
+        if self.num_loading() == 0 {
+            self.num_assets += 1;
+        }
+
+        if success {
+            self.num_finished += 1;
+        } else {
+            self.num_failed += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProgressCounter;
+
+    #[test]
+    fn announced_batch() {
+        let mut progress = ProgressCounter::new();
+        progress.add_assets(3);
+        assert_eq!(progress.num_loading(), 3);
+        assert!(!progress.is_complete());
+
+        progress.track(true);
+        progress.track(false);
+        assert_eq!(progress.num_loading(), 1);
+        assert_eq!(progress.num_finished(), 1);
+        assert_eq!(progress.num_failed(), 1);
+
+        progress.track(true);
+        assert_eq!(progress.num_assets(), 3);
+        assert!(progress.is_complete());
+    }
+
+    #[test]
+    fn unannounced_loads() {
+        let mut progress = ProgressCounter::new();
+        progress.track(true);
+        progress.track(true);
+        assert_eq!(progress.num_assets(), 2);
+        assert_eq!(progress.num_finished(), 2);
+        assert!(progress.is_complete());
+    }
+}
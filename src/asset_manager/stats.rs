@@ -0,0 +1,130 @@
+//! Statistics about loaded assets, e.g. for debug overlays.
+
+use fnv::FnvHashMap as HashMap;
+use std::collections::hash_map;
+use std::time::Duration;
+
+/// Information about a single loaded asset, returned by
+/// `AssetManager::asset_info`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AssetInfo {
+    /// Type string of the asset (usually its file extension).
+    pub asset_type: String,
+    /// Name of the store the asset was read from (see `AssetStore::name`),
+    /// or `None` if it was loaded from raw data.
+    pub store: Option<String>,
+    /// Size of the raw data of the asset in bytes.
+    pub num_bytes: usize,
+    /// Time spent decoding the asset.
+    pub load_time: Duration,
+    /// Names of the assets loaded along with this asset, like the nested
+    /// prefabs, meshes, and textures of a prefab.
+    pub dependencies: Vec<String>,
+}
+
+/// Statistics about the assets of a single asset type (like `"png"`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AssetTypeStats {
+    /// Number of assets loaded successfully.
+    pub num_loaded: usize,
+    /// Number of assets which failed to load.
+    pub num_failed: usize,
+    /// Total size of the raw data of the loaded assets in bytes.
+    pub num_bytes: usize,
+    /// Total time spent decoding the loaded assets.
+    pub load_time: Duration,
+}
+
+impl AssetTypeStats {
+    fn add(&mut self, other: &AssetTypeStats) {
+        self.num_loaded += other.num_loaded;
+        self.num_failed += other.num_failed;
+        self.num_bytes += other.num_bytes;
+        self.load_time += other.load_time;
+    }
+}
+
+/// Statistics about all assets loaded by an `AssetManager`, grouped by asset
+/// type. Returned by `AssetManager::stats`.
+#[derive(Clone, Debug, Default)]
+pub struct AssetStats {
+    types: HashMap<String, AssetTypeStats>,
+}
+
+impl AssetStats {
+    /// Creates new, empty statistics.
+    pub fn new() -> AssetStats {
+        AssetStats::default()
+    }
+
+    /// Returns the statistics of the given asset type, if any asset of that
+    /// type has been loaded.
+    pub fn get(&self, asset_type: &str) -> Option<&AssetTypeStats> {
+        self.types.get(asset_type)
+    }
+
+    /// Iterates over the statistics of all asset types.
+    pub fn iter(&self) -> hash_map::Iter<String, AssetTypeStats> {
+        self.types.iter()
+    }
+
+    /// Returns the statistics of all asset types added up.
+    pub fn total(&self) -> AssetTypeStats {
+        let mut total = AssetTypeStats::default();
+        for stats in self.types.values() {
+            total.add(stats);
+        }
+        total
+    }
+
+    /// Records a successful asset load. This is done automatically by the
+    /// `AssetManager`.
+    pub fn record_load(&mut self, asset_type: &str, num_bytes: usize, load_time: Duration) {
+        let stats = self.types.entry(asset_type.into()).or_insert_with(AssetTypeStats::default);
+        stats.num_loaded += 1;
+        stats.num_bytes += num_bytes;
+        stats.load_time += load_time;
+    }
+
+    /// Records a failed asset load. This is done automatically by the
+    /// `AssetManager`.
+    pub fn record_failure(&mut self, asset_type: &str) {
+        self.types.entry(asset_type.into()).or_insert_with(AssetTypeStats::default).num_failed += 1;
+    }
+
+    /// Resets all statistics, e.g. before measuring a level load.
+    pub fn clear(&mut self) {
+        self.types.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::AssetStats;
+
+    #[test]
+    fn add_up_types() {
+        let mut stats = AssetStats::new();
+        stats.record_load("png", 100, Duration::from_millis(2));
+        stats.record_load("png", 50, Duration::from_millis(1));
+        stats.record_load("obj", 20, Duration::from_millis(3));
+        stats.record_failure("obj");
+
+        let png = stats.get("png").unwrap();
+        assert_eq!(png.num_loaded, 2);
+        assert_eq!(png.num_bytes, 150);
+        assert_eq!(png.load_time, Duration::from_millis(3));
+        assert!(stats.get("wav").is_none());
+
+        let total = stats.total();
+        assert_eq!(total.num_loaded, 3);
+        assert_eq!(total.num_failed, 1);
+        assert_eq!(total.num_bytes, 170);
+        assert_eq!(total.load_time, Duration::from_millis(6));
+
+        stats.clear();
+        assert_eq!(stats.iter().count(), 0);
+    }
+}
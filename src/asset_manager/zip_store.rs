@@ -0,0 +1,176 @@
+//! Asset store reading from a zip archive.
+
+use fnv::FnvHashMap as HashMap;
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+use asset_manager::{AssetStore, AssetStoreError, normalize_path};
+
+/// Asset store representing a `.zip` archive, so all assets of a game can be
+/// shipped in a single file.
+///
+/// Asset names map to paths inside the archive the same way they map to
+/// files in a `DirectoryStore`. The central directory of the archive is read
+/// once on creation and kept in memory for lookups.
+pub struct ZipStore {
+    path: PathBuf,
+    archive: RefCell<ZipArchive<File>>,
+    /// Maps normalized paths to the paths inside the archive, if lookups
+    /// are normalized.
+    normalized: Option<HashMap<String, String>>,
+}
+
+impl ZipStore {
+    /// Opens the zip archive at the given path.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<ZipStore, AssetStoreError> {
+        let file = File::open(path.as_ref())?;
+        let archive = ZipArchive::new(file).map_err(zip_to_store_error)?;
+
+        Ok(ZipStore {
+            path: path.as_ref().to_path_buf(),
+            archive: RefCell::new(archive),
+            normalized: None,
+        })
+    }
+
+    /// Makes asset lookups ignore case and accept backslashes as separators,
+    /// see `normalize_path`. If several files in the archive normalize to the
+    /// same path, the last one wins.
+    pub fn with_normalized_paths(mut self) -> ZipStore {
+        let mut paths = HashMap::default();
+        {
+            let mut archive = self.archive.borrow_mut();
+            for index in 0..archive.len() {
+                if let Ok(file) = archive.by_index(index) {
+                    paths.insert(normalize_path(file.name()), file.name().to_string());
+                }
+            }
+        }
+
+        self.normalized = Some(paths);
+        self
+    }
+
+    /// Returns the path of an asset inside the archive given the asset's name
+    /// and type.
+    fn asset_to_path(&self, name: &str, asset_type: &str) -> String {
+        let path = format!("{}.{}", name, asset_type);
+        match self.normalized {
+            Some(ref paths) => paths.get(&normalize_path(&path)).cloned().unwrap_or(path),
+            None => path,
+        }
+    }
+}
+
+impl AssetStore for ZipStore {
+    fn name(&self) -> String {
+        format!("zip archive \"{}\"", self.path.display())
+    }
+
+    fn has_asset(&self, name: &str, asset_type: &str) -> bool {
+        let file_path = self.asset_to_path(name, asset_type);
+        let mut archive = self.archive.borrow_mut();
+        let found = archive.by_name(&file_path).is_ok();
+        found
+    }
+
+    fn load_asset(&self, name: &str, asset_type: &str, buf: &mut Vec<u8>) -> Result<usize, AssetStoreError> {
+        let file_path = self.asset_to_path(name, asset_type);
+        let mut archive = self.archive.borrow_mut();
+        let mut file = archive.by_name(&file_path).map_err(zip_to_store_error)?;
+        Ok(file.read_to_end(buf)?)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, AssetStoreError> {
+        let mut archive = self.archive.borrow_mut();
+        let mut names = Vec::new();
+        for index in 0..archive.len() {
+            let file = archive.by_index(index).map_err(zip_to_store_error)?;
+            let name = file.name();
+            if name.starts_with(prefix) && !name.ends_with('/') {
+                names.push(name.to_string());
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+}
+
+fn zip_to_store_error(e: ZipError) -> AssetStoreError {
+    match e {
+        ZipError::FileNotFound => AssetStoreError::NoSuchAsset,
+        ZipError::Io(e) => AssetStoreError::Io(e),
+        e => AssetStoreError::Io(io::Error::new(io::ErrorKind::InvalidData, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::{Cursor, Write};
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use zip::{CompressionMethod, ZipWriter};
+
+    use super::ZipStore;
+    use asset_manager::{AssetStore, AssetStoreError};
+
+    /// Writes a small archive to a temporary file and returns its path.
+    fn write_archive(name: &str) -> PathBuf {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for &(path, data) in &[("textures/grass.png", b"grass" as &[u8]),
+                                ("textures/Dirt.PNG", b"dirt"),
+                                ("meshes/cube.obj", b"cube")] {
+            writer.start_file(path, CompressionMethod::Stored).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        let archive = writer.finish().unwrap().into_inner();
+
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+        let path = env::temp_dir().join(format!("amethyst_{}_{}.zip", name, nanos));
+        File::create(&path).unwrap().write_all(&archive).unwrap();
+        path
+    }
+
+    #[test]
+    fn read_archive() {
+        let path = write_archive("read_archive");
+        let store = ZipStore::new(&path).unwrap();
+
+        assert!(store.has_asset("textures/grass", "png"));
+        assert!(!store.has_asset("textures/grass", "jpg"));
+
+        let mut buf = Vec::new();
+        assert_eq!(store.load_asset("meshes/cube", "obj", &mut buf).unwrap(), 4);
+        assert_eq!(buf, b"cube");
+        match store.load_asset("meshes/sphere", "obj", &mut buf) {
+            Err(AssetStoreError::NoSuchAsset) => (),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        assert_eq!(store.list("textures/").unwrap(),
+                   vec!["textures/Dirt.PNG".to_string(), "textures/grass.png".to_string()]);
+        assert!(store.list("sounds/").unwrap().is_empty());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn normalized_lookups() {
+        let path = write_archive("normalized_lookups");
+        let store = ZipStore::new(&path).unwrap().with_normalized_paths();
+
+        let mut buf = Vec::new();
+        assert!(store.has_asset("Textures\\dirt", "png"));
+        assert_eq!(store.load_asset("textures/dirt", "png", &mut buf).unwrap(), 4);
+        assert_eq!(buf, b"dirt");
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -76,6 +76,31 @@
 //! the value will be defaulted in addition to `display` being overwritten if
 //! you called `write_file()`.
 //!
+//! # Layered files
+//!
+//! `Element::from_layered_files` loads several files on top of each other,
+//! e.g. the game's shipped configuration followed by the player's overrides.
+//! Later files override the fields of earlier ones, nested structures are
+//! merged field by field, and files which don't exist are skipped. External
+//! files are looked up relative to the layer they are referenced in.
+//!
+//! ```ignore rust
+//! let config = DisplayConfig::from_layered_files(&["resources/display",
+//!                                                   "user/display"]);
+//! ```
+//!
+//! Fields can also be overridden from environment variables and command line
+//! arguments with `from_layered_files_with_overrides`, which is handy for CI
+//! and dedicated servers:
+//!
+//! ```ignore rust
+//! // AMETHYST_DISPLAY__FULLSCREEN=true game --display.vsync=false
+//! let overrides = [env_overrides("AMETHYST_DISPLAY"),
+//!                  arg_overrides(std::env::args(), "display")];
+//! let config = DisplayConfig::from_layered_files_with_overrides(&["resources/display"],
+//!                                                               &overrides);
+//! ```
+//!
 //! # Enums
 //!
 //! When `config!` is used on an enum type, it automatically implements the
@@ -139,12 +164,14 @@ extern crate yaml_rust;
 
 #[macro_use]
 mod definitions;
+mod overrides;
 mod yaml;
 
 use std::path::Path;
 
 pub use definitions::{ConfigMeta, ConfigError};
-pub use yaml::{Element, to_string};
+pub use overrides::{arg_overrides, env_overrides};
+pub use yaml::{Element, merge, to_string};
 pub use yaml_rust::Yaml;
 
 config! {
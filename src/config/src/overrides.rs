@@ -0,0 +1,194 @@
+//! Overriding configuration fields from environment variables and command
+//! line arguments.
+
+use std::collections::BTreeMap;
+use std::env;
+
+use yaml_rust::{Yaml, YamlLoader};
+
+use yaml::merge;
+
+/// Collects overrides from environment variables starting with `prefix`
+/// followed by one or two underscores. The rest of the variable name is the
+/// lowercase field path, with nested fields separated by double
+/// underscores.
+///
+/// E.g. with the prefix `AMETHYST`, the variable
+/// `AMETHYST_DISPLAY__FULLSCREEN=true` sets the `display.fullscreen` field.
+/// With the prefix `AMETHYST_DISPLAY`, both `AMETHYST_DISPLAY_FULLSCREEN` and
+/// `AMETHYST_DISPLAY__FULLSCREEN` set the `fullscreen` field.
+pub fn env_overrides(prefix: &str) -> Yaml {
+    let mut overrides = Yaml::Hash(BTreeMap::new());
+
+    for (name, value) in env::vars() {
+        if let Some(path) = env_path(&name, prefix) {
+            merge(&mut overrides, nest(&path, parse_value(&value)));
+        }
+    }
+
+    overrides
+}
+
+/// Collects overrides from command line arguments of the form
+/// `--<prefix>.<field path>=<value>`, with nested fields separated by dots.
+/// Other arguments are ignored.
+///
+/// E.g. with the prefix `display`, the argument `--display.fullscreen=true`
+/// sets the `fullscreen` field.
+pub fn arg_overrides<I>(args: I, prefix: &str) -> Yaml
+    where I: IntoIterator<Item = String>
+{
+    let prefix = format!("--{}.", prefix);
+    let mut overrides = Yaml::Hash(BTreeMap::new());
+
+    for arg in args {
+        if !arg.starts_with(&prefix) {
+            continue;
+        }
+
+        let mut split = arg[prefix.len()..].splitn(2, '=');
+        let path = split.next().unwrap_or("");
+        let value = match split.next() {
+            Some(value) if !path.is_empty() => value,
+            _ => continue,
+        };
+
+        let path: Vec<String> = path.split('.').map(|field| field.to_string()).collect();
+        merge(&mut overrides, nest(&path, parse_value(value)));
+    }
+
+    overrides
+}
+
+/// Returns the field path of the environment variable `name`, or `None` if
+/// it doesn't start with `prefix`.
+fn env_path(name: &str, prefix: &str) -> Option<Vec<String>> {
+    if !name.starts_with(prefix) {
+        return None;
+    }
+
+    let rest = &name[prefix.len()..];
+    let rest = if rest.starts_with("__") {
+        &rest[2..]
+    } else if rest.starts_with('_') {
+        &rest[1..]
+    } else {
+        return None;
+    };
+
+    if rest.is_empty() {
+        return None;
+    }
+
+    Some(rest.split("__").map(|field| field.to_lowercase()).collect())
+}
+
+/// Wraps `value` in hashes along `path`.
+fn nest(path: &[String], value: Yaml) -> Yaml {
+    path.iter().rev().fold(value, |value, field| {
+        let mut hash = BTreeMap::new();
+        hash.insert(Yaml::String(field.clone()), value);
+        Yaml::Hash(hash)
+    })
+}
+
+/// Parses `value` as YAML, so e.g. `true` and `[800, 600]` get their proper
+/// types, falling back to a string. Integers which aren't written the way
+/// YAML prints them, like `007`, are kept as strings so they aren't changed.
+fn parse_value(value: &str) -> Yaml {
+    let parsed = match YamlLoader::load_from_str(value) {
+        Ok(mut docs) => {
+            if docs.len() == 1 {
+                docs.remove(0)
+            } else {
+                return Yaml::String(value.to_string());
+            }
+        }
+        Err(_) => return Yaml::String(value.to_string()),
+    };
+
+    match parsed {
+        Yaml::Integer(integer) if integer.to_string() != value.trim() => {
+            Yaml::String(value.to_string())
+        }
+        parsed => parsed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::env;
+
+    use yaml_rust::{Yaml, YamlLoader};
+
+    use super::{arg_overrides, env_overrides, env_path, nest, parse_value};
+    use {ConfigMeta, DisplayConfig, Element};
+
+    fn yaml(src: &str) -> Yaml {
+        YamlLoader::load_from_str(src).unwrap().remove(0)
+    }
+
+    #[test]
+    fn parse_values() {
+        assert_eq!(parse_value("true"), Yaml::Boolean(true));
+        assert_eq!(parse_value("42"), Yaml::Integer(42));
+        assert_eq!(parse_value("[800, 600]"), yaml("[800, 600]"));
+        assert_eq!(parse_value("007"), Yaml::String("007".to_string()));
+        assert_eq!(parse_value("My Game"), Yaml::String("My Game".to_string()));
+    }
+
+    #[test]
+    fn nest_fields() {
+        let path = vec!["display".to_string(), "fullscreen".to_string()];
+        assert_eq!(nest(&path, Yaml::Boolean(true)), yaml("display:\n  fullscreen: true\n"));
+        assert_eq!(nest(&[], Yaml::Integer(1)), Yaml::Integer(1));
+    }
+
+    #[test]
+    fn env_variable_paths() {
+        let path = |fields: &[&str]| Some(fields.iter().map(|f| f.to_string()).collect::<Vec<_>>());
+        assert_eq!(env_path("AMETHYST_DISPLAY__FULLSCREEN", "AMETHYST"),
+                   path(&["display", "fullscreen"]));
+        assert_eq!(env_path("AMETHYST_DISPLAY__FULLSCREEN", "AMETHYST_DISPLAY"),
+                   path(&["fullscreen"]));
+        assert_eq!(env_path("AMETHYST_DISPLAY_MIN_DIMENSIONS", "AMETHYST_DISPLAY"),
+                   path(&["min_dimensions"]));
+        assert_eq!(env_path("AMETHYSTX_DISPLAY", "AMETHYST"), None);
+        assert_eq!(env_path("AMETHYST_", "AMETHYST"), None);
+        assert_eq!(env_path("OTHER_DISPLAY", "AMETHYST"), None);
+    }
+
+    #[test]
+    fn override_from_env() {
+        env::set_var("AMETHYST_TEST_ENV__DISPLAY__FULLSCREEN", "true");
+        env::set_var("AMETHYST_TEST_ENV__DISPLAY__TITLE", "007");
+        let overrides = env_overrides("AMETHYST_TEST_ENV");
+        env::remove_var("AMETHYST_TEST_ENV__DISPLAY__FULLSCREEN");
+        env::remove_var("AMETHYST_TEST_ENV__DISPLAY__TITLE");
+
+        assert_eq!(overrides, yaml("display:\n  fullscreen: true\n  title: \"007\"\n"));
+
+        env::set_var("AMETHYST_TEST_ENV_TITLE__TITLE", "42");
+        let overrides = env_overrides("AMETHYST_TEST_ENV_TITLE");
+        env::remove_var("AMETHYST_TEST_ENV_TITLE__TITLE");
+
+        let display = DisplayConfig::from_yaml(&ConfigMeta::default(), &overrides).unwrap();
+        assert_eq!(display.title, "42");
+    }
+
+    #[test]
+    fn override_from_args() {
+        let args = vec!["game".to_string(),
+                        "--display.fullscreen=true".to_string(),
+                        "--display.dimensions=[800, 600]".to_string(),
+                        "--display.=1".to_string(),
+                        "--logging.output_level=info".to_string()];
+        let overrides = arg_overrides(args, "display");
+
+        let mut expected = BTreeMap::new();
+        expected.insert(Yaml::String("fullscreen".to_string()), Yaml::Boolean(true));
+        expected.insert(Yaml::String("dimensions".to_string()), yaml("[800, 600]"));
+        assert_eq!(overrides, Yaml::Hash(expected));
+    }
+}
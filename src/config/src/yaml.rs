@@ -130,91 +130,197 @@ pub trait Element: Sized {
 
     /// From a file relative to current config
     fn from_file_raw<P: AsRef<Path>>(meta: &ConfigMeta, path: P) -> Result<Self, ConfigError> {
-        let path = path.as_ref();
-        let mut next_meta = meta.clone();
+        let (next_meta, hash) = load_file(meta, path.as_ref())?;
+        Self::from_yaml(&next_meta, &hash)
+    }
 
-        let initial_path = if next_meta.path.is_file() {
-            if let Some(parent) = next_meta.path.parent() {
-                parent.to_path_buf()
-            } else {
-                PathBuf::from("")
+    /// From a file relative to project
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let mut default = ConfigMeta::default();
+        default.path = PathBuf::from("");
+
+        Self::from_file_raw(&default, path)
+    }
+
+    /// From several files relative to project, where each file overrides the
+    /// fields set in the files before it, e.g. a base configuration followed
+    /// by the user's overrides. Nested configuration structures are merged
+    /// field by field. Files which don't exist are skipped, and fields which
+    /// aren't set in any file fall back to their defaults.
+    fn from_layered_files<P: AsRef<Path>>(paths: &[P]) -> Result<Self, ConfigError> {
+        Self::from_layered_files_with_overrides(paths, &[])
+    }
+
+    /// Like `from_layered_files`, with `overrides` merged on top of the files
+    /// in order, e.g. from `env_overrides` and `arg_overrides`.
+    fn from_layered_files_with_overrides<P: AsRef<Path>>(paths: &[P],
+                                                          overrides: &[Yaml])
+                                                          -> Result<Self, ConfigError> {
+        let mut default = ConfigMeta::default();
+        default.path = PathBuf::from("");
+
+        let mut meta = None;
+        let mut merged = Yaml::Hash(BTreeMap::new());
+        for path in paths {
+            match load_file(&default, path.as_ref()) {
+                Ok((layer_meta, mut layer)) => {
+                    inline_externs(&layer_meta, &mut Vec::new(), &mut layer)?;
+                    merge(&mut merged, layer);
+                    if meta.is_none() {
+                        meta = Some(layer_meta);
+                    }
+                }
+                Err(ConfigError::MissingExternalFile(_)) => {}
+                Err(e) => return Err(e),
             }
-        } else {
-            next_meta.path.clone()
-        };
+        }
 
-        let check = |list: &mut Vec<PathBuf>, file: &mut PathBuf| if file.exists() {
-            list.push(file.clone())
-        };
+        for layer in overrides {
+            merge(&mut merged, layer.clone());
+        }
 
-        let mut found = Vec::new();
+        Self::from_yaml(&meta.unwrap_or(default), &merged)
+    }
 
-        // file .yml
-        let mut file_path = initial_path.clone();
-        file_path.push(path);
-        file_path.set_extension("yml");
+    /// Recursively writes to files given the configuration's current context.
+    ///
+    /// The default path for a root configuration file is "config/config.yml".
+    ///
+    /// Note: This should never be called on a non-config! defined structure.
+    fn write_file(&self) -> Result<(), ConfigError> {
+        Err(ConfigError::NonConfig)
+    }
+}
 
-        // for proper error messages, displays the path it is looking for instead of parent
-        next_meta.path = file_path.clone();
+/// Merges `overrides` into `base`. Hashes are merged key by key, all other
+/// values are replaced.
+pub fn merge(base: &mut Yaml, overrides: Yaml) {
+    match (base, overrides) {
+        (&mut Yaml::Hash(ref mut base), Yaml::Hash(overrides)) => {
+            for (key, value) in overrides {
+                if let Some(existing) = base.get_mut(&key) {
+                    merge(existing, value);
+                    continue;
+                }
+                base.insert(key, value);
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
 
-        check(&mut found, &mut file_path);
+/// Replaces the `"extern"` fields of `yaml`, which was read from the file of
+/// `meta`, with the contents of the files they refer to, so that layers can
+/// be merged without losing track of where their external files are.
+/// `fields` is the path of `yaml` below the root of the configuration.
+/// External files which don't exist are removed, so they don't override the
+/// layers before.
+fn inline_externs(meta: &ConfigMeta,
+                  fields: &mut Vec<String>,
+                  yaml: &mut Yaml)
+                  -> Result<(), ConfigError> {
+    if let Yaml::Hash(ref mut hash) = *yaml {
+        let mut missing = Vec::new();
+        for (key, value) in hash.iter_mut() {
+            let field = match key.as_str() {
+                Some(field) => field.to_string(),
+                None => continue,
+            };
+
+            fields.push(field);
+            if value.as_str() == Some("extern") {
+                let path: PathBuf = fields.iter().collect();
+                match load_file(meta, &path) {
+                    Ok((extern_meta, mut extern_yaml)) => {
+                        inline_externs(&extern_meta, fields, &mut extern_yaml)?;
+                        *value = extern_yaml;
+                    }
+                    Err(ConfigError::MissingExternalFile(_)) => missing.push(key.clone()),
+                    Err(e) => return Err(e),
+                }
+            } else {
+                inline_externs(meta, fields, value)?;
+            }
+            fields.pop();
+        }
 
-        // file .yaml
-        file_path.set_extension("yaml");
-        check(&mut found, &mut file_path);
+        for key in missing {
+            hash.remove(&key);
+        }
+    }
 
-        // dir .yml
-        file_path.set_extension("");
-        file_path.push("config");
-        file_path.set_extension("yml");
-        check(&mut found, &mut file_path);
+    Ok(())
+}
 
-        // dir .yaml
-        file_path.set_extension("yaml");
-        check(&mut found, &mut file_path);
+/// Finds and parses a configuration file relative to `meta`, returning the
+/// meta data of the found file along with its contents.
+fn load_file(meta: &ConfigMeta, path: &Path) -> Result<(ConfigMeta, Yaml), ConfigError> {
+    let mut next_meta = meta.clone();
 
-        if found.len() > 1 {
-            return Err(ConfigError::MultipleExternalFiles(path.to_path_buf(), found));
-        } else if found.len() == 0 {
-            return Err(ConfigError::MissingExternalFile(next_meta.clone()));
+    let initial_path = if next_meta.path.is_file() {
+        if let Some(parent) = next_meta.path.parent() {
+            parent.to_path_buf()
+        } else {
+            PathBuf::from("")
         }
+    } else {
+        next_meta.path.clone()
+    };
 
-        let found_path = found[0].clone();
-        next_meta.path = found_path.clone();
+    let check = |list: &mut Vec<PathBuf>, file: &mut PathBuf| if file.exists() {
+        list.push(file.clone())
+    };
 
-        let mut file = File::open(found_path.as_path()).map_err(|e| ConfigError::FileError(found_path.clone(), e))?;
-        let mut buffer = String::new();
+    let mut found = Vec::new();
 
-        file.read_to_string(&mut buffer)
-            .map_err(|e| ConfigError::FileError(found_path.clone(), e))?;
+    // file .yml
+    let mut file_path = initial_path.clone();
+    file_path.push(path);
+    file_path.set_extension("yml");
 
-        let yaml = YamlLoader::load_from_str(&buffer).map_err(|e| ConfigError::YamlScan(e))?;
+    // for proper error messages, displays the path it is looking for instead of parent
+    next_meta.path = file_path.clone();
 
-        let hash = if yaml.len() > 0 {
-            yaml[0].clone()
-        } else {
-            Yaml::Hash(BTreeMap::new())
-        };
+    check(&mut found, &mut file_path);
 
-        Self::from_yaml(&next_meta, &hash)
-    }
+    // file .yaml
+    file_path.set_extension("yaml");
+    check(&mut found, &mut file_path);
 
-    /// From a file relative to project
-    fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
-        let mut default = ConfigMeta::default();
-        default.path = PathBuf::from("");
+    // dir .yml
+    file_path.set_extension("");
+    file_path.push("config");
+    file_path.set_extension("yml");
+    check(&mut found, &mut file_path);
 
-        Self::from_file_raw(&default, path)
-    }
+    // dir .yaml
+    file_path.set_extension("yaml");
+    check(&mut found, &mut file_path);
 
-    /// Recursively writes to files given the configuration's current context.
-    ///
-    /// The default path for a root configuration file is "config/config.yml".
-    ///
-    /// Note: This should never be called on a non-config! defined structure.
-    fn write_file(&self) -> Result<(), ConfigError> {
-        Err(ConfigError::NonConfig)
+    if found.len() > 1 {
+        return Err(ConfigError::MultipleExternalFiles(path.to_path_buf(), found));
+    } else if found.len() == 0 {
+        return Err(ConfigError::MissingExternalFile(next_meta.clone()));
     }
+
+    let found_path = found[0].clone();
+    next_meta.path = found_path.clone();
+
+    let mut file = File::open(found_path.as_path()).map_err(|e| ConfigError::FileError(found_path.clone(), e))?;
+    let mut buffer = String::new();
+
+    file.read_to_string(&mut buffer)
+        .map_err(|e| ConfigError::FileError(found_path.clone(), e))?;
+
+    let yaml = YamlLoader::load_from_str(&buffer).map_err(|e| ConfigError::YamlScan(e))?;
+
+    let hash = if yaml.len() > 0 {
+        yaml[0].clone()
+    } else {
+        Yaml::Hash(BTreeMap::new())
+    };
+
+    Ok((next_meta, hash))
 }
 
 macro_rules! yaml_int {
@@ -273,11 +379,15 @@ impl Element for bool {
 }
 
 impl Element for String {
+    /// Also accepts numbers and booleans as their text, e.g. for overrides
+    /// like `AMETHYST_DISPLAY__TITLE=42`.
     fn from_yaml(meta: &ConfigMeta, config: &Yaml) -> Result<Self, ConfigError> {
-        if let &Yaml::String(ref string) = config {
-            Ok(string.clone())
-        } else {
-            Err(ConfigError::YamlParse(meta.clone()))
+        match config {
+            &Yaml::String(ref string) |
+            &Yaml::Real(ref string) => Ok(string.clone()),
+            &Yaml::Integer(integer) => Ok(integer.to_string()),
+            &Yaml::Boolean(boolean) => Ok(boolean.to_string()),
+            _ => Err(ConfigError::YamlParse(meta.clone())),
         }
     }
 
@@ -520,3 +630,76 @@ macro_rules! yaml_set {
 
 yaml_set!(HashSet: Hash Eq);
 yaml_set!(BTreeSet: Ord);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use yaml_rust::{Yaml, YamlLoader};
+
+    use super::{Element, merge};
+    use Config;
+
+    fn yaml(src: &str) -> Yaml {
+        YamlLoader::load_from_str(src).unwrap().remove(0)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+        let dir = env::temp_dir().join(format!("amethyst_config_{}_{}", name, nanos));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        File::create(path).unwrap().write_all(contents.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn merge_precedence() {
+        let mut base = yaml("a: 1\nnested:\n  b: 2\n  c: 3\n");
+        merge(&mut base, yaml("nested:\n  c: 4\nd: [5]\n"));
+        assert_eq!(base, yaml("a: 1\nnested:\n  b: 2\n  c: 4\nd: [5]\n"));
+
+        merge(&mut base, yaml("nested: 6\n"));
+        assert_eq!(base["nested"], Yaml::Integer(6));
+    }
+
+    #[test]
+    fn skip_missing_layers() {
+        let dir = temp_dir("skip_missing_layers");
+        write(&dir.join("base.yml"), "a: 1\nb: 2\n");
+        write(&dir.join("user.yml"), "b: 3\n");
+
+        let layers = [dir.join("base"), dir.join("missing"), dir.join("user")];
+        let loaded = BTreeMap::<String, i64>::from_layered_files(&layers);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded["a"], 1);
+        assert_eq!(loaded["b"], 3);
+    }
+
+    #[test]
+    fn resolve_externs_per_layer() {
+        let dir = temp_dir("resolve_externs_per_layer");
+        write(&dir.join("base.yml"), "display: \"extern\"\n");
+        write(&dir.join("display.yml"), "title: \"Base\"\nfullscreen: true\n");
+        write(&dir.join("user/config.yml"), "display: \"extern\"\n");
+        write(&dir.join("user/display.yml"), "title: \"User\"\n");
+
+        let layers = [dir.join("base"), dir.join("user")];
+        let config = Config::from_layered_files(&layers);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let config = config.unwrap();
+        assert_eq!(config.display.title, "User");
+        assert!(config.display.fullscreen);
+    }
+}
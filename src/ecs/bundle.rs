@@ -0,0 +1,22 @@
+//! Packaged registration of systems, components, and resources.
+
+use ecs::Planner;
+
+/// A set of systems together with the component types and resources they
+/// need, registered in one call with `ApplicationBuilder::with_bundle`.
+///
+/// ```ignore
+/// struct PhysicsBundle;
+///
+/// impl SystemBundle for PhysicsBundle {
+///     fn build(self, planner: &mut Planner<()>) {
+///         planner.mut_world().register::<Velocity>();
+///         planner.add_system(PhysicsSystem::new(), "physics_system", 1);
+///     }
+/// }
+/// ```
+pub trait SystemBundle {
+    /// Registers the bundle's component types and resources with the world
+    /// of `planner`, and adds its systems to `planner`.
+    fn build(self, planner: &mut Planner<()>);
+}
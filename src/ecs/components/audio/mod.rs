@@ -0,0 +1,5 @@
+//! Audio resources.
+
+mod source;
+
+pub use self::source::{AudioFormat, AudioSource};
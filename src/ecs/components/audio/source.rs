@@ -0,0 +1,59 @@
+//! Encoded audio resource handling.
+
+use asset_manager::{AssetLoader, AssetLoaderRaw, Assets, LoaderError};
+
+/// Encodings of sound files understood by `AudioSource`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AudioFormat {
+    /// Waveform audio (`.wav`).
+    Wav,
+    /// Ogg Vorbis (`.ogg`).
+    Ogg,
+    /// Free Lossless Audio Codec (`.flac`).
+    Flac,
+}
+
+impl AudioFormat {
+    /// Detects the format of a sound file from its magic bytes.
+    pub fn detect(data: &[u8]) -> Option<AudioFormat> {
+        if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WAVE" {
+            Some(AudioFormat::Wav)
+        } else if data.starts_with(b"OggS") {
+            Some(AudioFormat::Ogg)
+        } else if data.starts_with(b"fLaC") {
+            Some(AudioFormat::Flac)
+        } else {
+            None
+        }
+    }
+}
+
+/// A sound effect or music track. The data is kept encoded and is only
+/// decoded when it's played, which keeps long tracks small in memory.
+#[derive(Clone, Debug)]
+pub struct AudioSource {
+    /// Encoding of `bytes`.
+    pub format: AudioFormat,
+    /// Contents of the sound file.
+    pub bytes: Vec<u8>,
+}
+
+impl AssetLoaderRaw for AudioSource {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<AudioSource, LoaderError> {
+        match AudioFormat::detect(data) {
+            Some(format) => {
+                Ok(AudioSource {
+                    format: format,
+                    bytes: data.to_vec(),
+                })
+            }
+            None => Err("Unknown audio format".into()),
+        }
+    }
+}
+
+impl AssetLoader<AudioSource> for AudioSource {
+    fn from_data(_: &mut Assets, source: AudioSource) -> Result<AudioSource, LoaderError> {
+        Ok(source)
+    }
+}
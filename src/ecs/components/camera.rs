@@ -0,0 +1,51 @@
+//! Camera component.
+
+use ecs::{Component, VecStorage};
+use ecs::resources::Projection;
+
+/// Makes an entity a camera, looking along its negative z axis as placed by
+/// its `Transform`. Select it for rendering with the `ActiveCamera`
+/// resource.
+///
+/// Viewports aren't supported yet. The camera always renders to the whole
+/// window.
+#[derive(Clone, Copy)]
+pub struct CameraView {
+    /// Graphical projection of the camera.
+    pub proj: Projection,
+}
+
+impl CameraView {
+    /// Creates a new camera with the projection `proj`.
+    pub fn new(proj: Projection) -> CameraView {
+        CameraView { proj: proj }
+    }
+
+    /// Creates an orthographic camera for 2D games, showing `width` by
+    /// `height` units with the origin in the bottom left corner.
+    pub fn standard_2d(width: f32, height: f32) -> CameraView {
+        CameraView::new(Projection::Orthographic {
+            left: 0.0,
+            right: width,
+            bottom: 0.0,
+            top: height,
+            near: 0.1,
+            far: 2000.0,
+        })
+    }
+
+    /// Creates a perspective camera for 3D games, with a field of view of 60
+    /// degrees and the aspect ratio of a `width` by `height` screen.
+    pub fn standard_3d(width: f32, height: f32) -> CameraView {
+        CameraView::new(Projection::Perspective {
+            fov: 60.0,
+            aspect_ratio: width / height,
+            near: 0.1,
+            far: 2000.0,
+        })
+    }
+}
+
+impl Component for CameraView {
+    type Storage = VecStorage<CameraView>;
+}
@@ -0,0 +1,22 @@
+//! Visibility components.
+
+use ecs::{Component, NullStorage};
+
+/// Hides an entity from rendering.
+#[derive(Default, Copy, Clone)]
+pub struct Hidden;
+
+impl Component for Hidden {
+    type Storage = NullStorage<Hidden>;
+}
+
+/// Hides an entity and all of its descendants in the `Child` hierarchy, e.g.
+/// a character together with its attached equipment. `HideHierarchySystem`
+/// adds `Hidden` to the descendants and removes it again once this component
+/// is removed.
+#[derive(Default, Copy, Clone)]
+pub struct HiddenPropagate;
+
+impl Component for HiddenPropagate {
+    type Storage = NullStorage<HiddenPropagate>;
+}
@@ -1,7 +1,21 @@
 //! Standard library of useful components.
 
+mod audio;
+mod camera;
+mod hidden;
 mod rendering;
+mod tag;
+mod timer;
 mod transform;
 
-pub use self::rendering::{Mesh, Renderable, Texture, TextureLoadData};
+pub use self::audio::{AudioFormat, AudioSource};
+pub use self::camera::CameraView;
+pub use self::hidden::{Hidden, HiddenPropagate};
+pub use self::rendering::{AnimationFinished, Font, FontFormat, Mesh, PlayMode, Renderable,
+                          ShaderSource, Sprite, SpriteAnimation, SpriteSheet, Texture,
+                          TextureLoadData};
+#[cfg(feature="tiled")]
+pub use self::rendering::TileMap;
+pub use self::tag::{Tag, WithTag};
+pub use self::timer::{Timer, TimerEvent};
 pub use self::transform::{Child, Init, InnerTransform, Transform, LocalTransform};
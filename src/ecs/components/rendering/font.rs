@@ -0,0 +1,55 @@
+//! Font resource handling.
+
+use asset_manager::{AssetLoader, AssetLoaderRaw, Assets, LoaderError};
+
+/// Outline formats of font files understood by `Font`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FontFormat {
+    /// TrueType outlines (`.ttf`).
+    TrueType,
+    /// OpenType font with CFF outlines (`.otf`).
+    OpenType,
+}
+
+impl FontFormat {
+    /// Detects the format of a font file from its magic bytes.
+    pub fn detect(data: &[u8]) -> Option<FontFormat> {
+        if data.starts_with(&[0, 1, 0, 0]) || data.starts_with(b"true") {
+            Some(FontFormat::TrueType)
+        } else if data.starts_with(b"OTTO") {
+            Some(FontFormat::OpenType)
+        } else {
+            None
+        }
+    }
+}
+
+/// A font, kept as the contents of its font file for text rendering to
+/// rasterize glyphs from.
+#[derive(Clone, Debug)]
+pub struct Font {
+    /// Outline format of `bytes`.
+    pub format: FontFormat,
+    /// Contents of the font file.
+    pub bytes: Vec<u8>,
+}
+
+impl AssetLoaderRaw for Font {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<Font, LoaderError> {
+        match FontFormat::detect(data) {
+            Some(format) => {
+                Ok(Font {
+                    format: format,
+                    bytes: data.to_vec(),
+                })
+            }
+            None => Err("Unknown font format".into()),
+        }
+    }
+}
+
+impl AssetLoader<Font> for Font {
+    fn from_data(_: &mut Assets, font: Font) -> Result<Font, LoaderError> {
+        Ok(font)
+    }
+}
@@ -3,7 +3,7 @@
 use gfx;
 use gfx::traits::FactoryExt;
 
-use asset_manager::{AssetLoader, Assets};
+use asset_manager::{AssetLoader, Assets, LoaderError};
 use gfx_device::gfx_types;
 use renderer::VertexPosNormal;
 
@@ -20,11 +20,11 @@ impl AssetLoader<Mesh> for Vec<VertexPosNormal> {
     /// # Panics
     ///
     /// Panics if factory isn't registered as loader.
-    fn from_data(assets: &mut Assets, data: Vec<VertexPosNormal>) -> Option<Mesh> {
+    fn from_data(assets: &mut Assets, data: Vec<VertexPosNormal>) -> Result<Mesh, LoaderError> {
         let factory = assets.get_loader_mut::<gfx_types::Factory>()
             .expect("Couldn't retrieve factory.");
         let (buffer, slice) = factory.create_vertex_buffer_with_slice(&data, ());
-        Some(Mesh {
+        Ok(Mesh {
             buffer: buffer,
             slice: slice,
         })
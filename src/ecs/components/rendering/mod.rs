@@ -1,9 +1,21 @@
 //! Components for the rendering processor.
 
+mod font;
 mod mesh;
 mod renderable;
+mod shader_source;
+mod sprite_animation;
+mod sprite_sheet;
 mod texture;
+#[cfg(feature="tiled")]
+mod tile_map;
 
+pub use self::font::{Font, FontFormat};
 pub use self::mesh::*;
 pub use self::renderable::Renderable;
+pub use self::shader_source::ShaderSource;
+pub use self::sprite_animation::{AnimationFinished, PlayMode, SpriteAnimation};
+pub use self::sprite_sheet::{Sprite, SpriteSheet};
 pub use self::texture::*;
+#[cfg(feature="tiled")]
+pub use self::tile_map::TileMap;
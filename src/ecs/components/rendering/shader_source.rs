@@ -0,0 +1,20 @@
+//! Shader source resource handling.
+
+use asset_manager::{AssetLoader, Assets, LoaderError};
+
+/// GLSL source code of a shader with all `#include "file.glsl"` directives
+/// expanded, see `AssetManager::load_shader`. It can be compiled for custom
+/// render passes.
+#[derive(Clone, Debug)]
+pub struct ShaderSource {
+    /// The expanded source code.
+    pub source: String,
+    /// Paths of all files included by the shader, directly or indirectly.
+    pub includes: Vec<String>,
+}
+
+impl AssetLoader<ShaderSource> for ShaderSource {
+    fn from_data(_: &mut Assets, shader: ShaderSource) -> Result<ShaderSource, LoaderError> {
+        Ok(shader)
+    }
+}
@@ -0,0 +1,207 @@
+//! Frame-by-frame sprite animation.
+
+use std::time::Duration;
+
+use ecs::{Component, Entity, VecStorage};
+use ecs::components::{Sprite, SpriteSheet};
+
+/// How a `SpriteAnimation` continues after its last frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlayMode {
+    /// Stop at the last frame.
+    Once,
+    /// Start over at the first frame.
+    Loop,
+    /// Play backwards to the first frame, then forwards again.
+    PingPong,
+}
+
+/// Animates an entity by stepping through sprites of a `SpriteSheet`.
+/// Driven by `SpriteAnimationSystem`, which advances the frames using the
+/// `Time` resource and publishes an `AnimationFinished` event through the
+/// `Broadcaster` resource when an animation played with `PlayMode::Once`
+/// ends.
+#[derive(Clone, Debug)]
+pub struct SpriteAnimation {
+    /// Indices of the frames in `SpriteSheet::sprites`.
+    pub frames: Vec<usize>,
+    /// Display time of every frame. If there are fewer durations than
+    /// frames, the last duration is used for the remaining frames. A frame
+    /// with a zero duration is held until the animation is reset, and an
+    /// animation without durations stays on its first frame.
+    pub durations: Vec<Duration>,
+    /// What happens after the last frame.
+    pub mode: PlayMode,
+    /// Whether the animation is paused.
+    pub paused: bool,
+    current: usize,
+    backwards: bool,
+    elapsed: Duration,
+    finished: bool,
+}
+
+impl SpriteAnimation {
+    /// Creates an animation showing every frame for `frame_duration`.
+    pub fn new(frames: Vec<usize>, frame_duration: Duration, mode: PlayMode) -> SpriteAnimation {
+        SpriteAnimation::with_durations(frames, vec![frame_duration], mode)
+    }
+
+    /// Creates an animation with a display time per frame.
+    pub fn with_durations(frames: Vec<usize>,
+                          durations: Vec<Duration>,
+                          mode: PlayMode)
+                          -> SpriteAnimation {
+        SpriteAnimation {
+            frames: frames,
+            durations: durations,
+            mode: mode,
+            paused: false,
+            current: 0,
+            backwards: false,
+            elapsed: Duration::new(0, 0),
+            finished: false,
+        }
+    }
+
+    /// Returns the position of the current frame in `frames`.
+    pub fn current_frame(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the index of the current sprite in `SpriteSheet::sprites`.
+    pub fn current_sprite_index(&self) -> Option<usize> {
+        self.frames.get(self.current).cloned()
+    }
+
+    /// Returns the current sprite of `sheet`.
+    pub fn current_sprite<'a>(&self, sheet: &'a SpriteSheet) -> Option<&'a Sprite> {
+        self.current_sprite_index().and_then(|index| sheet.sprites.get(index))
+    }
+
+    /// Returns `true` if an animation played with `PlayMode::Once` has
+    /// reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Starts the animation over from the first frame.
+    pub fn reset(&mut self) {
+        self.current = 0;
+        self.backwards = false;
+        self.elapsed = Duration::new(0, 0);
+        self.finished = false;
+    }
+
+    /// Advances the animation by `delta`, returning `true` if it finished
+    /// during this step.
+    pub fn advance(&mut self, delta: Duration) -> bool {
+        if self.paused || self.finished || self.frames.is_empty() {
+            return false;
+        }
+
+        self.elapsed += delta;
+        loop {
+            let duration = self.frame_duration(self.current);
+            if duration == Duration::new(0, 0) || self.elapsed < duration {
+                return false;
+            }
+
+            self.elapsed -= duration;
+            if self.next_frame() {
+                self.finished = true;
+                self.elapsed = Duration::new(0, 0);
+                return true;
+            }
+        }
+    }
+
+    fn frame_duration(&self, frame: usize) -> Duration {
+        match self.durations.get(frame).or_else(|| self.durations.last()) {
+            Some(&duration) => duration,
+            None => Duration::new(0, 0),
+        }
+    }
+
+    /// Steps to the next frame, returning `true` if the animation ended.
+    fn next_frame(&mut self) -> bool {
+        let last = self.frames.len() - 1;
+        match self.mode {
+            PlayMode::Once if self.current == last => return true,
+            PlayMode::Once => self.current += 1,
+            PlayMode::Loop => self.current = if self.current == last { 0 } else { self.current + 1 },
+            PlayMode::PingPong => {
+                if last == 0 {
+                    return false;
+                }
+                if (self.backwards && self.current == 0) || (!self.backwards && self.current == last) {
+                    self.backwards = !self.backwards;
+                }
+                if self.backwards {
+                    self.current -= 1;
+                } else {
+                    self.current += 1;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl Component for SpriteAnimation {
+    type Storage = VecStorage<SpriteAnimation>;
+}
+
+/// Published through the `Broadcaster` resource by `SpriteAnimationSystem`
+/// when an animation played with `PlayMode::Once` ends.
+#[derive(Clone, Copy, Debug)]
+pub struct AnimationFinished {
+    /// Entity the finished `SpriteAnimation` belongs to.
+    pub entity: Entity,
+}
+
+impl Component for AnimationFinished {
+    type Storage = VecStorage<AnimationFinished>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlayMode, SpriteAnimation};
+    use std::time::Duration;
+
+    fn frames(animation: &mut SpriteAnimation, steps: usize) -> Vec<usize> {
+        (0..steps)
+            .map(|_| {
+                animation.advance(Duration::from_millis(10));
+                animation.current_sprite_index().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn play_modes() {
+        let mut once = SpriteAnimation::new(vec![4, 5, 6], Duration::from_millis(10), PlayMode::Once);
+        assert_eq!(frames(&mut once, 2), vec![5, 6]);
+        assert!(once.advance(Duration::from_millis(10)));
+        assert!(once.is_finished());
+        assert_eq!(once.current_sprite_index(), Some(6));
+
+        let mut looped = SpriteAnimation::new(vec![4, 5, 6], Duration::from_millis(10), PlayMode::Loop);
+        assert_eq!(frames(&mut looped, 4), vec![5, 6, 4, 5]);
+
+        let mut ping_pong = SpriteAnimation::new(vec![4, 5, 6], Duration::from_millis(10), PlayMode::PingPong);
+        assert_eq!(frames(&mut ping_pong, 5), vec![5, 6, 5, 4, 5]);
+    }
+
+    #[test]
+    fn frame_durations() {
+        let durations = vec![Duration::from_millis(10), Duration::from_millis(30)];
+        let mut animation = SpriteAnimation::with_durations(vec![0, 1, 2], durations, PlayMode::Loop);
+        animation.advance(Duration::from_millis(20));
+        assert_eq!(animation.current_frame(), 1);
+        animation.advance(Duration::from_millis(20));
+        assert_eq!(animation.current_frame(), 2);
+        animation.advance(Duration::from_millis(30));
+        assert_eq!(animation.current_frame(), 0);
+    }
+}
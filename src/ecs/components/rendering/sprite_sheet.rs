@@ -0,0 +1,99 @@
+//! Sprite sheet resource handling.
+
+#[cfg(feature="serde_json")]
+use serde_json::Value;
+
+use asset_manager::{AssetLoader, Assets, LoaderError};
+#[cfg(feature="serde_json")]
+use asset_manager::AssetLoaderRaw;
+
+/// A named rectangle in the texture of a sprite sheet, in pixels.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sprite {
+    /// Name of the sprite, usually the file name of the original image.
+    pub name: String,
+    /// Left edge of the sprite.
+    pub x: u32,
+    /// Top edge of the sprite.
+    pub y: u32,
+    /// Width of the sprite.
+    pub width: u32,
+    /// Height of the sprite.
+    pub height: u32,
+}
+
+/// Several sprites packed into a single texture, see
+/// `AssetManager::load_sprite_sheet`.
+///
+/// With the `serde_json` feature, sprite sheets can be loaded from the JSON
+/// (hash or array) files exported by TexturePacker.
+#[derive(Clone, Debug)]
+pub struct SpriteSheet {
+    /// Path of the texture, relative to the sprite sheet.
+    pub texture: String,
+    /// Width of the texture in pixels.
+    pub width: u32,
+    /// Height of the texture in pixels.
+    pub height: u32,
+    /// The sprites of the sheet.
+    pub sprites: Vec<Sprite>,
+}
+
+impl SpriteSheet {
+    /// Returns the sprite with the given name.
+    pub fn sprite(&self, name: &str) -> Option<&Sprite> {
+        self.sprites.iter().find(|sprite| sprite.name == name)
+    }
+}
+
+#[cfg(feature="serde_json")]
+impl AssetLoaderRaw for SpriteSheet {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<SpriteSheet, LoaderError> {
+        let json: Value = ::serde_json::from_slice(data)?;
+        let meta = &json["meta"];
+        let texture = meta["image"].as_str().ok_or("Missing texture path")?;
+
+        let frames: Vec<(&str, &Value)> = match json["frames"] {
+            Value::Object(ref frames) => frames.iter().map(|(name, frame)| (name.as_str(), frame)).collect(),
+            Value::Array(ref frames) => {
+                frames.iter()
+                    .map(|frame| (frame["filename"].as_str().unwrap_or(""), frame))
+                    .collect()
+            }
+            _ => return Err("Missing frames".into()),
+        };
+
+        let mut sprites = Vec::new();
+        for (name, frame) in frames {
+            let rect = &frame["frame"];
+            sprites.push(Sprite {
+                name: name.into(),
+                x: json_u32(&rect["x"])?,
+                y: json_u32(&rect["y"])?,
+                width: json_u32(&rect["w"])?,
+                height: json_u32(&rect["h"])?,
+            });
+        }
+
+        Ok(SpriteSheet {
+            texture: texture.into(),
+            width: json_u32(&meta["size"]["w"])?,
+            height: json_u32(&meta["size"]["h"])?,
+            sprites: sprites,
+        })
+    }
+}
+
+#[cfg(feature="serde_json")]
+fn json_u32(value: &Value) -> Result<u32, LoaderError> {
+    match value.as_u64() {
+        Some(n) if n <= u32::max_value() as u64 => Ok(n as u32),
+        _ => Err(format!("Expected a pixel coordinate, found {}", value).into()),
+    }
+}
+
+impl AssetLoader<SpriteSheet> for SpriteSheet {
+    fn from_data(_: &mut Assets, sheet: SpriteSheet) -> Result<SpriteSheet, LoaderError> {
+        Ok(sheet)
+    }
+}
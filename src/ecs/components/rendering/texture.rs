@@ -3,7 +3,7 @@
 use gfx::Factory;
 use gfx::format::{Formatted, SurfaceTyped};
 
-use asset_manager::{AssetLoader, Assets};
+use asset_manager::{AssetLoader, Assets, LoaderError};
 use gfx_device::gfx_types;
 use renderer;
 use renderer::target::ColorFormat;
@@ -29,20 +29,20 @@ impl<'a> AssetLoader<Texture> for TextureLoadData<'a> {
     /// # Panics
     ///
     /// Panics if factory isn't registered as loader.
-    fn from_data(assets: &mut Assets, data: TextureLoadData) -> Option<Texture> {
+    fn from_data(assets: &mut Assets, data: TextureLoadData) -> Result<Texture, LoaderError> {
         let factory = assets.get_loader_mut::<gfx_types::Factory>()
             .expect("Couldn't retrieve factory.");
         let tex_res_view =
             match factory.create_texture_immutable::<ColorFormat>(data.kind, data.raw) {
                 Ok((_, tex_res_view)) => tex_res_view,
-                Err(_) => return None,
+                Err(e) => return Err(format!("Failed to create texture: {:?}", e).into()),
             };
-        Some(renderer::Texture::Texture(tex_res_view))
+        Ok(renderer::Texture::Texture(tex_res_view))
     }
 }
 
 impl AssetLoader<Texture> for [f32; 4] {
-    fn from_data(_: &mut Assets, color: [f32; 4]) -> Option<Texture> {
-        Some(renderer::Texture::Constant(color))
+    fn from_data(_: &mut Assets, color: [f32; 4]) -> Result<Texture, LoaderError> {
+        Ok(renderer::Texture::Constant(color))
     }
 }
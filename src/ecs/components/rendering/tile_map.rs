@@ -0,0 +1,63 @@
+//! Tile map resource handling.
+
+use std::io::Cursor;
+use tiled;
+
+use asset_manager::{AssetLoader, AssetLoaderRaw, Assets, LoaderError};
+
+/// A map authored in the [Tiled](http://www.mapeditor.org/) editor, with its
+/// layers, tilesets, and object groups. See `AssetManager::load_tile_map`.
+///
+/// Tilesets have to be embedded into the map. Maps referencing external
+/// `.tsx` tilesets fail to load with an error naming the tileset.
+pub type TileMap = tiled::Map;
+
+impl AssetLoaderRaw for TileMap {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<TileMap, LoaderError> {
+        if let Some(source) = external_tileset(&String::from_utf8_lossy(data)) {
+            return Err(format!("External tileset \"{}\" isn't supported, embed it into the map",
+                               source)
+                .into());
+        }
+
+        tiled::parse(Cursor::new(data)).map_err(|e| format!("Invalid TMX data: {:?}", e).into())
+    }
+}
+
+/// Returns the `source` of the first `<tileset>` element which references an
+/// external tileset file.
+fn external_tileset(data: &str) -> Option<String> {
+    let mut rest = data;
+    while let Some(start) = rest.find("<tileset") {
+        rest = &rest[start + "<tileset".len()..];
+        let tag = &rest[..rest.find('>').unwrap_or(rest.len())];
+        if let Some(index) = tag.find("source=\"") {
+            let value = &tag[index + "source=\"".len()..];
+            return Some(value[..value.find('"').unwrap_or(value.len())].to_string());
+        }
+    }
+
+    None
+}
+
+impl AssetLoader<TileMap> for TileMap {
+    fn from_data(_: &mut Assets, map: TileMap) -> Result<TileMap, LoaderError> {
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::external_tileset;
+
+    #[test]
+    fn detect_external_tilesets() {
+        let embedded = r#"<map><tileset firstgid="1" name="grass">
+                              <image source="grass.png"/>
+                          </tileset></map>"#;
+        assert_eq!(external_tileset(embedded), None);
+
+        let external = r#"<map><tileset firstgid="1" source="../tilesets/grass.tsx"/></map>"#;
+        assert_eq!(external_tileset(external), Some("../tilesets/grass.tsx".to_string()));
+    }
+}
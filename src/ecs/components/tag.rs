@@ -0,0 +1,85 @@
+//! Generic marker component.
+
+use std::any::Any;
+use std::marker::PhantomData;
+
+use ecs::{Component, EntityBuilder, NullStorage};
+
+/// Zero-sized component marking entities with the type `T`, so joins like
+/// "all enemies" don't need a marker component of their own.
+///
+/// ```ignore
+/// struct Enemy;
+///
+/// world.register::<Tag<Enemy>>();
+/// world.create_now().with(LocalTransform::default()).with_tag::<Enemy>().build();
+///
+/// let enemies = world.read::<Tag<Enemy>>();
+/// for (local, _) in (&locals, &enemies).iter() {
+///     // ...
+/// }
+/// ```
+pub struct Tag<T> {
+    marker: PhantomData<T>,
+}
+
+impl<T> Tag<T> {
+    /// Creates a new tag.
+    pub fn new() -> Tag<T> {
+        Tag { marker: PhantomData }
+    }
+}
+
+impl<T> Default for Tag<T> {
+    fn default() -> Tag<T> {
+        Tag::new()
+    }
+}
+
+impl<T> Clone for Tag<T> {
+    fn clone(&self) -> Tag<T> {
+        Tag::new()
+    }
+}
+
+impl<T> Copy for Tag<T> {}
+
+impl<T: Any + Send + Sync> Component for Tag<T> {
+    type Storage = NullStorage<Tag<T>>;
+}
+
+/// Extension of `EntityBuilder` for adding `Tag`s.
+pub trait WithTag {
+    /// Adds a `Tag<T>` to the entity.
+    fn with_tag<T: Any + Send + Sync>(self) -> Self;
+}
+
+impl<'a> WithTag for EntityBuilder<'a> {
+    fn with_tag<T: Any + Send + Sync>(self) -> EntityBuilder<'a> {
+        self.with(Tag::<T>::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tag, WithTag};
+    use ecs::{Join, World};
+
+    struct Enemy;
+    struct Pickup;
+
+    #[test]
+    fn join_tagged_entities() {
+        let mut world = World::new();
+        world.register::<Tag<Enemy>>();
+        world.register::<Tag<Pickup>>();
+
+        let enemy = world.create_now().with_tag::<Enemy>().build();
+        world.create_now().with_tag::<Pickup>().build();
+
+        let entities = world.entities();
+        let enemies = world.read::<Tag<Enemy>>();
+        let tagged = (&entities, &enemies).iter().map(|(entity, _)| entity).collect::<Vec<_>>();
+        assert_eq!(tagged, vec![enemy]);
+    }
+}
@@ -0,0 +1,139 @@
+//! Countdown timers in game time.
+
+use std::time::Duration;
+
+use ecs::{Component, Entity, VecStorage};
+
+/// Counts down in game time and fires a `TimerEvent` through the
+/// `Broadcaster` resource when it expires. Useful for cooldowns and spawn
+/// intervals. Driven by `TimerSystem`, so it respects `Time::time_scale` and
+/// `Time::paused`.
+#[derive(Clone, Debug)]
+pub struct Timer {
+    /// Time until the timer expires.
+    pub duration: Duration,
+    /// Whether the timer starts over after expiring.
+    pub repeat: bool,
+    /// Whether the timer is paused.
+    pub paused: bool,
+    elapsed: Duration,
+    finished: bool,
+}
+
+impl Timer {
+    /// Creates a timer which expires once after `duration`.
+    pub fn new(duration: Duration) -> Timer {
+        Timer {
+            duration: duration,
+            repeat: false,
+            paused: false,
+            elapsed: Duration::new(0, 0),
+            finished: false,
+        }
+    }
+
+    /// Creates a timer which expires every `duration`.
+    pub fn repeating(duration: Duration) -> Timer {
+        Timer { repeat: true, ..Timer::new(duration) }
+    }
+
+    /// Returns the time elapsed since the timer was started or last expired.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the time left until the timer expires.
+    pub fn remaining(&self) -> Duration {
+        if self.elapsed >= self.duration {
+            Duration::new(0, 0)
+        } else {
+            self.duration - self.elapsed
+        }
+    }
+
+    /// Returns `true` if a non-repeating timer has expired.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Starts the timer over.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::new(0, 0);
+        self.finished = false;
+    }
+
+    /// Advances the timer by `delta`, returning how many times it expired.
+    pub fn tick(&mut self, delta: Duration) -> u32 {
+        if self.paused || self.finished {
+            return 0;
+        }
+
+        self.elapsed += delta;
+        if self.elapsed < self.duration {
+            return 0;
+        }
+
+        if !self.repeat || self.duration == Duration::new(0, 0) {
+            self.elapsed = if self.repeat { Duration::new(0, 0) } else { self.duration };
+            self.finished = !self.repeat;
+            return 1;
+        }
+
+        let mut expired = 0;
+        while self.elapsed >= self.duration {
+            self.elapsed -= self.duration;
+            expired += 1;
+        }
+        expired
+    }
+}
+
+impl Component for Timer {
+    type Storage = VecStorage<Timer>;
+}
+
+/// Published through the `Broadcaster` resource by `TimerSystem` when a
+/// `Timer` expires. Published events can be read by states until the systems
+/// are dispatched again in the next frame.
+#[derive(Clone, Copy, Debug)]
+pub struct TimerEvent {
+    /// Entity the expired `Timer` belongs to.
+    pub entity: Entity,
+    /// How many times the timer expired during the frame, which can be more
+    /// than one for short repeating timers.
+    pub count: u32,
+}
+
+impl Component for TimerEvent {
+    type Storage = VecStorage<TimerEvent>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Timer;
+    use std::time::Duration;
+
+    #[test]
+    fn expire_once() {
+        let mut timer = Timer::new(Duration::from_millis(100));
+        assert_eq!(timer.tick(Duration::from_millis(60)), 0);
+        assert_eq!(timer.remaining(), Duration::from_millis(40));
+        assert_eq!(timer.tick(Duration::from_millis(60)), 1);
+        assert!(timer.is_finished());
+        assert_eq!(timer.tick(Duration::from_millis(200)), 0);
+
+        timer.reset();
+        assert_eq!(timer.tick(Duration::from_millis(100)), 1);
+    }
+
+    #[test]
+    fn repeat() {
+        let mut timer = Timer::repeating(Duration::from_millis(10));
+        assert_eq!(timer.tick(Duration::from_millis(35)), 3);
+        assert_eq!(timer.elapsed(), Duration::from_millis(5));
+        assert!(!timer.is_finished());
+
+        timer.paused = true;
+        assert_eq!(timer.tick(Duration::from_millis(35)), 0);
+    }
+}
@@ -4,17 +4,18 @@ use cgmath::{Quaternion, Vector3, Matrix3, Matrix4};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::ops::{Deref, DerefMut};
 
+use Float;
 use ecs::{Component, VecStorage};
 
 /// Raw transform data.
 #[derive(Debug)]
 pub struct InnerTransform {
     /// Translation/position vector [x, y, z]
-    pub translation: [f32; 3],
+    pub translation: [Float; 3],
     /// Quaternion [w (scalar), x, y, z]
-    pub rotation: [f32; 4],
+    pub rotation: [Float; 4],
     /// Scale vector [x, y, z]
-    pub scale: [f32; 3],
+    pub scale: [Float; 3],
 }
 
 /// Local position, rotation, and scale (from parent if it exists).
@@ -63,15 +64,15 @@ impl LocalTransform {
     /// Combined with the parent's global `Transform` component it gives
     /// the global (or world) matrix for the current entity.
     #[inline]
-    pub fn matrix(&self) -> [[f32; 4]; 4] {
-        let quat: Matrix3<f32> = Quaternion::from(self.rotation).into();
-        let scale: Matrix3<f32> = Matrix3::<f32> {
+    pub fn matrix(&self) -> [[Float; 4]; 4] {
+        let quat: Matrix3<Float> = Quaternion::from(self.rotation).into();
+        let scale: Matrix3<Float> = Matrix3::<Float> {
             x: [self.scale[0], 0.0, 0.0].into(),
             y: [0.0, self.scale[1], 0.0].into(),
             z: [0.0, 0.0, self.scale[2]].into(),
         };
-        let mut matrix: Matrix4<f32> = (&quat * scale).into();
-        matrix.w = Vector3::from(self.translation).extend(1.0f32);
+        let mut matrix: Matrix4<Float> = (&quat * scale).into();
+        matrix.w = Vector3::from(self.translation).extend(1.0);
         matrix.into()
     }
 }
@@ -1,12 +1,26 @@
 //! Global transform component.
 
+use Float;
 use ecs::{Component, VecStorage};
 
 /// Performs a global transformation on the entity (transform from origin).
 ///
 /// Used for rendering position and orientation.
 #[derive(Debug, Copy, Clone)]
-pub struct Transform(pub [[f32; 4]; 4]);
+pub struct Transform(pub [[Float; 4]; 4]);
+
+impl Transform {
+    /// Returns the matrix in single precision, as used by the renderer.
+    pub fn render_matrix(&self) -> [[f32; 4]; 4] {
+        let mut matrix = [[0.0; 4]; 4];
+        for (row, source) in matrix.iter_mut().zip(self.0.iter()) {
+            for (value, source) in row.iter_mut().zip(source.iter()) {
+                *value = *source as f32;
+            }
+        }
+        matrix
+    }
+}
 
 impl Component for Transform {
     type Storage = VecStorage<Transform>;
@@ -21,14 +35,14 @@ impl Default for Transform {
     }
 }
 
-impl From<[[f32; 4]; 4]> for Transform {
-    fn from(matrix: [[f32; 4]; 4]) -> Self {
+impl From<[[Float; 4]; 4]> for Transform {
+    fn from(matrix: [[Float; 4]; 4]) -> Self {
         Transform(matrix)
     }
 }
 
-impl Into<[[f32; 4]; 4]> for Transform {
-    fn into(self) -> [[f32; 4]; 4] {
+impl Into<[[Float; 4]; 4]> for Transform {
+    fn into(self) -> [[Float; 4]; 4] {
         self.0
     }
 }
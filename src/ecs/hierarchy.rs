@@ -0,0 +1,66 @@
+//! Working with the `Child` hierarchy.
+
+use fnv::FnvHashSet as HashSet;
+
+use ecs::{Entity, Join, World};
+use ecs::components::Child;
+
+/// Extension of `World` for deleting entities together with their
+/// descendants.
+pub trait DeleteHierarchy {
+    /// Deletes `entity` and every entity below it in the `Child` hierarchy,
+    /// e.g. a prefab root together with all of its children.
+    fn delete_hierarchy(&mut self, entity: Entity);
+}
+
+impl DeleteHierarchy for World {
+    fn delete_hierarchy(&mut self, entity: Entity) {
+        let mut doomed = HashSet::default();
+        doomed.insert(entity);
+
+        {
+            let entities = self.entities();
+            let children = self.read::<Child>();
+
+            // Keeps collecting children of doomed entities until a pass
+            // doesn't find any new ones.
+            let mut found = true;
+            while found {
+                found = false;
+                for (child_entity, child) in (&entities, &children).iter() {
+                    if doomed.contains(&child.parent()) && doomed.insert(child_entity) {
+                        found = true;
+                    }
+                }
+            }
+        }
+
+        for entity in doomed {
+            self.delete_now(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeleteHierarchy;
+    use ecs::World;
+    use ecs::components::Child;
+
+    #[test]
+    fn delete_descendants() {
+        let mut world = World::new();
+        world.register::<Child>();
+
+        let root = world.create_now().build();
+        let child = world.create_now().with(Child::new(root)).build();
+        let grandchild = world.create_now().with(Child::new(child)).build();
+        let other = world.create_now().build();
+
+        world.delete_hierarchy(root);
+        assert!(!world.is_alive(root));
+        assert!(!world.is_alive(child));
+        assert!(!world.is_alive(grandchild));
+        assert!(world.is_alive(other));
+    }
+}
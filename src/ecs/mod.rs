@@ -4,4 +4,11 @@ pub mod components;
 pub mod resources;
 pub mod systems;
 
+mod bundle;
+mod hierarchy;
+mod system_graph;
+
 pub use specs::*;
+pub use self::bundle::SystemBundle;
+pub use self::hierarchy::DeleteHierarchy;
+pub use self::system_graph::{SystemGraph, SystemGraphError};
@@ -1,5 +1,8 @@
 //! World resource for an orthographic or perspective projection camera.
 
+use ecs::Entity;
+use renderer;
+
 /// Represents the graphical projection of a `Camera`.
 #[derive(Copy, Clone)]
 pub enum Projection {
@@ -35,6 +38,20 @@ pub enum Projection {
     },
 }
 
+impl Projection {
+    /// Returns the projection matrix.
+    pub fn matrix(&self) -> [[f32; 4]; 4] {
+        match *self {
+            Projection::Perspective { fov, aspect_ratio, near, far } => {
+                renderer::Camera::perspective(fov, aspect_ratio, near, far)
+            }
+            Projection::Orthographic { left, right, bottom, top, near, far } => {
+                renderer::Camera::orthographic(left, right, bottom, top, near, far)
+            }
+        }
+    }
+}
+
 /// Represents a camera looking around inside a game world.
 #[derive(Copy, Clone)]
 pub struct Camera {
@@ -60,3 +77,12 @@ impl Camera {
         }
     }
 }
+
+/// Selects the entity with a `CameraView` component and a `Transform` which
+/// is used for rendering. If no entity is selected, the `Camera` resource is
+/// used instead.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ActiveCamera {
+    /// The camera entity.
+    pub entity: Option<Entity>,
+}
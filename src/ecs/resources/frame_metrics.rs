@@ -0,0 +1,125 @@
+//! Per-frame timings of the main loop.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Time spent in the phases of a single frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameTimings {
+    /// Time spent polling and handling window and user events.
+    pub handle_events: Duration,
+    /// Number of times `State::fixed_update` was called.
+    pub fixed_updates: u32,
+    /// Time spent in `State::fixed_update`.
+    pub fixed_update: Duration,
+    /// Time spent in `State::update`.
+    pub update: Duration,
+    /// Time spent dispatching and waiting for systems.
+    pub dispatch: Duration,
+    /// Time spent rendering and presenting the frame.
+    pub render: Duration,
+}
+
+impl FrameTimings {
+    /// Returns the time spent in all phases.
+    pub fn total(&self) -> Duration {
+        self.handle_events + self.fixed_update + self.update + self.dispatch + self.render
+    }
+}
+
+/// Rolling record of the `FrameTimings` of the last frames, added to
+/// `ecs::World` as a resource by default and updated by `Application` at the
+/// end of every frame. Useful for overlays and benchmarks.
+pub struct FrameMetrics {
+    capacity: usize,
+    frames: VecDeque<FrameTimings>,
+}
+
+impl Default for FrameMetrics {
+    fn default() -> FrameMetrics {
+        FrameMetrics::new(120)
+    }
+}
+
+impl FrameMetrics {
+    /// Creates a record keeping the timings of the last `capacity` frames.
+    pub fn new(capacity: usize) -> FrameMetrics {
+        FrameMetrics {
+            capacity: capacity.max(1),
+            frames: VecDeque::with_capacity(capacity.max(1)),
+        }
+    }
+
+    /// Adds the timings of a frame, dropping the oldest ones if the record is
+    /// full.
+    pub fn record(&mut self, timings: FrameTimings) {
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(timings);
+    }
+
+    /// Returns the timings of the last recorded frame.
+    pub fn latest(&self) -> Option<&FrameTimings> {
+        self.frames.back()
+    }
+
+    /// Iterates over the recorded timings, oldest first.
+    pub fn iter(&self) -> ::std::collections::vec_deque::Iter<FrameTimings> {
+        self.frames.iter()
+    }
+
+    /// Returns the average timings of the recorded frames. The number of
+    /// fixed updates is rounded down.
+    pub fn average(&self) -> Option<FrameTimings> {
+        let count = self.frames.len() as u32;
+        if count == 0 {
+            return None;
+        }
+
+        let mut sum = FrameTimings::default();
+        for frame in &self.frames {
+            sum.handle_events += frame.handle_events;
+            sum.fixed_updates += frame.fixed_updates;
+            sum.fixed_update += frame.fixed_update;
+            sum.update += frame.update;
+            sum.dispatch += frame.dispatch;
+            sum.render += frame.render;
+        }
+
+        Some(FrameTimings {
+            handle_events: sum.handle_events / count,
+            fixed_updates: sum.fixed_updates / count,
+            fixed_update: sum.fixed_update / count,
+            update: sum.update / count,
+            dispatch: sum.dispatch / count,
+            render: sum.render / count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameMetrics, FrameTimings};
+    use std::time::Duration;
+
+    #[test]
+    fn rolling_average() {
+        let mut metrics = FrameMetrics::new(2);
+        for millis in 1..4 {
+            metrics.record(FrameTimings {
+                update: Duration::from_millis(millis),
+                fixed_updates: 1,
+                ..FrameTimings::default()
+            });
+        }
+
+        assert_eq!(metrics.iter().count(), 2);
+        assert_eq!(metrics.latest().unwrap().update, Duration::from_millis(3));
+
+        let average = metrics.average().unwrap();
+        assert_eq!(average.update, Duration::new(0, 2_500_000));
+        assert_eq!(average.fixed_updates, 1);
+        assert_eq!(average.total(), Duration::new(0, 2_500_000));
+    }
+}
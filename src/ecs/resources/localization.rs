@@ -0,0 +1,226 @@
+//! World resource for translating user-facing text.
+
+use fnv::FnvHashMap as HashMap;
+use std::str;
+
+use asset_manager::{AssetLoader, AssetLoaderRaw, Assets, LoaderError};
+
+/// Translated messages of a single language, loaded from a
+/// [Fluent](http://projectfluent.org/) `.ftl` file.
+///
+/// Only the basic subset of Fluent is supported: messages, terms (`-brand`),
+/// attributes (`.title`), multiline values, and placeables referencing
+/// variables (`{ $name }`), terms, and other messages. Selectors and
+/// functions aren't supported.
+#[derive(Clone, Debug, Default)]
+pub struct Locale {
+    messages: HashMap<String, String>,
+}
+
+impl Locale {
+    /// Parses a locale from Fluent source.
+    pub fn parse(source: &str) -> Result<Locale, String> {
+        let mut messages = HashMap::default();
+        let mut current: Option<String> = None;
+
+        for (number, line) in source.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let indented = line.starts_with(' ');
+            if indented && trimmed.starts_with('.') {
+                // Attribute of the current message, stored as `message.attr`.
+                let message = match current {
+                    Some(ref message) => message.split('.').next().unwrap().to_string(),
+                    None => return Err(format!("Attribute without message on line {}", number + 1)),
+                };
+                let (attr, value) = parse_entry(&trimmed[1..], number)?;
+                let id = format!("{}.{}", message, attr);
+                messages.insert(id.clone(), value);
+                current = Some(id);
+            } else if indented {
+                // Continuation of a multiline value.
+                match current {
+                    Some(ref id) => {
+                        let value = messages.get_mut(id).unwrap();
+                        if !value.is_empty() {
+                            value.push('\n');
+                        }
+                        value.push_str(trimmed);
+                    }
+                    None => return Err(format!("Unexpected indentation on line {}", number + 1)),
+                }
+            } else {
+                let (id, value) = parse_entry(line, number)?;
+                messages.insert(id.clone(), value);
+                current = Some(id);
+            }
+        }
+
+        Ok(Locale { messages: messages })
+    }
+
+    /// Returns the raw value of a message, without resolving placeables.
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.messages.get(id).map(|value| value.as_str())
+    }
+
+    /// Returns the message with the given id, replacing placeables with the
+    /// values of `args` and referenced terms or messages. Unknown references
+    /// are left in the text, so missing arguments are easy to spot.
+    pub fn format(&self, id: &str, args: &[(&str, &str)]) -> Option<String> {
+        self.get(id).map(|value| self.resolve(value, args, 0))
+    }
+
+    fn resolve(&self, value: &str, args: &[(&str, &str)], depth: usize) -> String {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find('{') {
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => break,
+            };
+            result.push_str(&rest[..start]);
+
+            let placeable = rest[start + 1..end].trim();
+            let resolved = if placeable.starts_with('$') {
+                args.iter().find(|&&(name, _)| name == &placeable[1..]).map(|&(_, arg)| arg.to_string())
+            } else if depth < 8 {
+                self.get(placeable).map(|value| self.resolve(value, args, depth + 1))
+            } else {
+                None
+            };
+
+            match resolved {
+                Some(text) => result.push_str(&text),
+                None => result.push_str(&rest[start..end + 1]),
+            }
+            rest = &rest[end + 1..];
+        }
+
+        result.push_str(rest);
+        result
+    }
+}
+
+/// Parses an `id = value` line.
+fn parse_entry(line: &str, number: usize) -> Result<(String, String), String> {
+    let index = match line.find('=') {
+        Some(index) => index,
+        None => return Err(format!("Expected `=` on line {}", number + 1)),
+    };
+
+    let id = line[..index].trim();
+    let valid = id.trim_left_matches('-')
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    if id.is_empty() || !valid {
+        return Err(format!("Invalid identifier \"{}\" on line {}", id, number + 1));
+    }
+
+    Ok((id.into(), line[index + 1..].trim().into()))
+}
+
+impl AssetLoaderRaw for Locale {
+    fn from_raw(_: &Assets, data: &[u8]) -> Result<Locale, LoaderError> {
+        let source = str::from_utf8(data)?;
+        Ok(Locale::parse(source)?)
+    }
+}
+
+impl AssetLoader<Locale> for Locale {
+    fn from_data(_: &mut Assets, locale: Locale) -> Result<Locale, LoaderError> {
+        Ok(locale)
+    }
+}
+
+/// Selects the active language and translates messages into it.
+///
+/// Messages missing from the active language are looked up in the fallback
+/// language, which is the language the `Localization` was created with.
+pub struct Localization {
+    language: String,
+    fallback: String,
+    locales: HashMap<String, Locale>,
+}
+
+impl Localization {
+    /// Creates a new localization with the given active and fallback
+    /// language, e.g. `"en-US"`.
+    pub fn new(language: &str) -> Localization {
+        Localization {
+            language: language.into(),
+            fallback: language.into(),
+            locales: HashMap::default(),
+        }
+    }
+
+    /// Adds the messages of a language, replacing any messages added for the
+    /// language before.
+    pub fn insert(&mut self, language: &str, locale: Locale) {
+        self.locales.insert(language.into(), locale);
+    }
+
+    /// Returns the active language.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Sets the active language.
+    pub fn set_language(&mut self, language: &str) {
+        self.language = language.into();
+    }
+
+    /// Translates a message into the active language, see `Locale::format`.
+    pub fn format(&self, id: &str, args: &[(&str, &str)]) -> Option<String> {
+        [&self.language, &self.fallback]
+            .iter()
+            .filter_map(|language| self.locales.get(language.as_str()))
+            .filter_map(|locale| locale.format(id, args))
+            .next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Locale, Localization};
+
+    const EN: &'static str = "
+# Comment
+-brand = Amethyst
+welcome = Welcome to { -brand }, { $name }!
+credits =
+    Made with { -brand }.
+    Thanks for playing.
+menu = Menu
+    .title = Main menu
+";
+
+    #[test]
+    fn format_messages() {
+        let locale = Locale::parse(EN).unwrap();
+        assert_eq!(locale.format("welcome", &[("name", "Ferris")]).unwrap(),
+                   "Welcome to Amethyst, Ferris!");
+        assert_eq!(locale.format("welcome", &[]).unwrap(),
+                   "Welcome to Amethyst, { $name }!");
+        assert_eq!(locale.format("credits", &[]).unwrap(),
+                   "Made with Amethyst.\nThanks for playing.");
+        assert_eq!(locale.get("menu.title"), Some("Main menu"));
+        assert!(Locale::parse("no equals sign").is_err());
+    }
+
+    #[test]
+    fn fall_back_to_default_language() {
+        let mut localization = Localization::new("en-US");
+        localization.insert("en-US", Locale::parse(EN).unwrap());
+        localization.insert("de-DE", Locale::parse("menu = Menü").unwrap());
+        localization.set_language("de-DE");
+
+        assert_eq!(localization.format("menu", &[]).unwrap(), "Menü");
+        assert_eq!(localization.format("credits", &[]).unwrap(),
+                   "Made with Amethyst.\nThanks for playing.");
+        assert_eq!(localization.format("missing", &[]), None);
+    }
+}
@@ -1,16 +1,22 @@
 //! Resources that can be added to `ecs::World`.
 //!
-//! `Camera`, `ScreenDimensions`, and `Time` are added by default and
-//! automatically updated every frame by `Application`.
+//! `Camera`, `FrameMetrics`, `ScreenDimensions`, and `Time` are added by
+//! default and automatically updated every frame by `Application`.
+//! `Broadcaster` is added by default as well and cleaned right before the
+//! systems are dispatched.
 
 mod camera;
+mod frame_metrics;
 mod screen_dimensions;
 mod time;
 mod input;
+mod localization;
 mod broadcaster;
 
 pub use self::broadcaster::Broadcaster;
-pub use self::camera::{Camera, Projection};
+pub use self::camera::{ActiveCamera, Camera, Projection};
+pub use self::frame_metrics::{FrameMetrics, FrameTimings};
 pub use self::input::InputHandler;
+pub use self::localization::{Locale, Localization};
 pub use self::screen_dimensions::ScreenDimensions;
 pub use self::time::Time;
@@ -6,10 +6,166 @@ use std::time::{Duration, Instant};
 /// `Time` is added to `ecs::World` as a resource by default.
 /// It is updated every frame in `Application::advance_frame`.
 pub struct Time {
-    /// Time elapsed since the last frame.
+    /// Time elapsed since the last frame, multiplied by `time_scale`. This is
+    /// zero while the game is paused.
     pub delta_time: Duration,
+    /// Time elapsed since the last frame, ignoring `time_scale` and `paused`.
+    pub delta_real_time: Duration,
     /// Rate at which `State::fixed_update` is called.
     pub fixed_step: Duration,
     /// Time at which `State::fixed_update` was last called.
     pub last_fixed_update: Instant,
+    /// Number of fixed steps which were run in the current frame. Systems
+    /// doing physics-style work should step this many times per frame.
+    pub fixed_steps: u32,
+    /// Maximum number of fixed steps run in one frame. Accumulated time
+    /// beyond that is dropped, so a slow frame can't make the following
+    /// frames ever slower by running more and more fixed steps.
+    pub max_fixed_steps: u32,
+    /// Scaled time elapsed since the application started.
+    pub absolute_time: Duration,
+    /// Number of frames since the application started.
+    pub frame_number: u64,
+    /// Multiplier applied to `delta_time`, e.g. 0.5 for slow motion.
+    pub time_scale: f32,
+    /// Whether game time is paused. While paused `delta_time` is zero and no
+    /// fixed steps are run.
+    pub paused: bool,
+    /// Scaled time which hasn't been consumed by fixed steps yet.
+    fixed_accumulator: Duration,
+}
+
+impl Default for Time {
+    fn default() -> Time {
+        Time {
+            delta_time: Duration::new(0, 0),
+            delta_real_time: Duration::new(0, 0),
+            fixed_step: Duration::new(0, 16666666),
+            last_fixed_update: Instant::now(),
+            fixed_steps: 0,
+            max_fixed_steps: 5,
+            absolute_time: Duration::new(0, 0),
+            frame_number: 0,
+            time_scale: 1.0,
+            paused: false,
+            fixed_accumulator: Duration::new(0, 0),
+        }
+    }
+}
+
+impl Time {
+    /// Returns `delta_time` in seconds.
+    pub fn delta_seconds(&self) -> f32 {
+        duration_to_secs(self.delta_time) as f32
+    }
+
+    /// Returns `fixed_step` in seconds.
+    pub fn fixed_seconds(&self) -> f32 {
+        duration_to_secs(self.fixed_step) as f32
+    }
+
+    /// Returns `absolute_time` in seconds.
+    pub fn absolute_seconds(&self) -> f64 {
+        duration_to_secs(self.absolute_time)
+    }
+
+    /// Starts a new frame which took `elapsed` of real time, applying the
+    /// time scale and pause state and adding to the fixed step accumulator.
+    pub fn advance_frame(&mut self, elapsed: Duration) {
+        self.delta_real_time = elapsed;
+        self.delta_time = if self.paused {
+            Duration::new(0, 0)
+        } else {
+            secs_to_duration(duration_to_secs(elapsed) * self.time_scale.max(0.0) as f64)
+        };
+        self.absolute_time += self.delta_time;
+        self.frame_number += 1;
+        self.fixed_accumulator += self.delta_time;
+        self.fixed_steps = 0;
+    }
+
+    /// Consumes one fixed step from the accumulator, returning `false` once
+    /// there is not enough accumulated time left for another step or
+    /// `max_fixed_steps` were run this frame.
+    pub fn step_fixed_update(&mut self) -> bool {
+        if self.fixed_step == Duration::new(0, 0) || self.fixed_accumulator < self.fixed_step {
+            return false;
+        }
+
+        if self.fixed_steps >= self.max_fixed_steps {
+            self.fixed_accumulator = Duration::new(0, 0);
+            return false;
+        }
+
+        self.fixed_accumulator -= self.fixed_step;
+        self.fixed_steps += 1;
+        self.last_fixed_update = Instant::now();
+        true
+    }
+}
+
+fn duration_to_secs(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + duration.subsec_nanos() as f64 / 1.0e9
+}
+
+fn secs_to_duration(secs: f64) -> Duration {
+    let whole = secs.trunc();
+    Duration::new(whole as u64, ((secs - whole) * 1.0e9).round() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Time;
+    use std::time::Duration;
+
+    #[test]
+    fn scale_and_pause() {
+        let mut time = Time::default();
+        time.time_scale = 0.5;
+        time.advance_frame(Duration::from_millis(100));
+        assert_eq!(time.delta_time, Duration::from_millis(50));
+        assert_eq!(time.delta_real_time, Duration::from_millis(100));
+
+        time.paused = true;
+        time.advance_frame(Duration::from_millis(100));
+        assert_eq!(time.delta_time, Duration::new(0, 0));
+        assert_eq!(time.absolute_time, Duration::from_millis(50));
+        assert_eq!(time.frame_number, 2);
+    }
+
+    #[test]
+    fn accumulate_fixed_steps() {
+        let mut time = Time::default();
+        time.fixed_step = Duration::from_millis(10);
+        time.advance_frame(Duration::from_millis(25));
+
+        let mut steps = 0;
+        while time.step_fixed_update() {
+            steps += 1;
+        }
+        assert_eq!(steps, 2);
+        assert_eq!(time.fixed_steps, 2);
+
+        time.advance_frame(Duration::from_millis(5));
+        assert!(time.step_fixed_update());
+        assert!(!time.step_fixed_update());
+    }
+
+    #[test]
+    fn limit_fixed_steps() {
+        let mut time = Time::default();
+        time.fixed_step = Duration::from_millis(10);
+        time.max_fixed_steps = 3;
+        time.advance_frame(Duration::from_millis(1000));
+
+        let mut steps = 0;
+        while time.step_fixed_update() {
+            steps += 1;
+        }
+        assert_eq!(steps, 3);
+
+        time.advance_frame(Duration::from_millis(10));
+        assert!(time.step_fixed_update());
+        assert!(!time.step_fixed_update());
+    }
 }
@@ -0,0 +1,284 @@
+//! Registering systems by name with dependencies.
+
+use std::error::Error;
+use std::fmt;
+
+use fnv::FnvHashMap as HashMap;
+
+use ecs::{Planner, Priority, System};
+
+/// An error returned by `SystemGraph::build`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SystemGraphError {
+    /// Two systems were added with the same name.
+    DuplicateName(String),
+    /// A system depends on a name no system was added with, or `with_order`
+    /// was given such a name.
+    MissingDependency {
+        /// Name of the depending system, or for an unknown `after` name of
+        /// `with_order`, the name of the system it was ordered after.
+        system: String,
+        /// The missing name.
+        dependency: String,
+    },
+    /// The dependencies of the named system form a cycle.
+    Cycle(String),
+}
+
+impl fmt::Display for SystemGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SystemGraphError::DuplicateName(ref name) => {
+                write!(f, "system `{}` was added twice", name)
+            }
+            SystemGraphError::MissingDependency { ref system, ref dependency } => {
+                write!(f, "system `{}` depends on missing system `{}`", system, dependency)
+            }
+            SystemGraphError::Cycle(ref name) => {
+                write!(f, "system `{}` depends on itself", name)
+            }
+        }
+    }
+}
+
+impl Error for SystemGraphError {
+    fn description(&self) -> &str {
+        match *self {
+            SystemGraphError::DuplicateName(_) => "Duplicate system name",
+            SystemGraphError::MissingDependency { .. } => "Missing system dependency",
+            SystemGraphError::Cycle(_) => "Cyclic system dependencies",
+        }
+    }
+}
+
+struct Node {
+    name: String,
+    dependencies: Vec<String>,
+    add: Box<FnMut(&mut Planner<()>, &str, Priority)>,
+}
+
+/// Builds a set of systems which are registered by name together with the
+/// names of the systems they have to run after, instead of by priority.
+///
+/// `build` checks for missing names and cycles, then adds the systems to a
+/// `Planner` with priorities which dispatch every system after its
+/// dependencies.
+///
+/// Note that priorities only order the acquisition of component and resource
+/// locks, not execution. A system sees the changes of its dependencies to
+/// the data they share, but systems without shared data may still run in
+/// parallel and finish in any order.
+///
+/// ```ignore
+/// let graph = SystemGraph::new()
+///     .with(InputSystem, "input", &[])
+///     .with(MovementSystem, "movement", &["input"])
+///     .with(CollisionSystem, "collision", &["movement"]);
+///
+/// let app = Application::build(state, cfg)
+///     .with_system_graph(graph)?
+///     .done();
+/// ```
+#[derive(Default)]
+pub struct SystemGraph {
+    nodes: Vec<Node>,
+    orders: Vec<(String, String)>,
+}
+
+impl SystemGraph {
+    /// Creates an empty system graph.
+    pub fn new() -> SystemGraph {
+        SystemGraph::default()
+    }
+
+    /// Adds `system` under `name`, running after all systems named in
+    /// `dependencies`.
+    pub fn with<S>(mut self, system: S, name: &str, dependencies: &[&str]) -> SystemGraph
+        where S: System<()> + 'static
+    {
+        let mut system = Some(system);
+        self.nodes.push(Node {
+            name: name.to_string(),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            add: Box::new(move |planner, name, priority| if let Some(system) = system.take() {
+                planner.add_system(system, name, priority);
+            }),
+        });
+        self
+    }
+
+    /// Makes the system named `after` run after the one named `before`. This
+    /// allows e.g. bundles to splice their systems in between others. Both
+    /// names are checked by `build`, like dependencies.
+    pub fn with_order(mut self, before: &str, after: &str) -> SystemGraph {
+        self.orders.push((before.to_string(), after.to_string()));
+        self
+    }
+
+    /// Checks the dependencies and adds all systems to `planner`. Systems
+    /// get priorities from `base` upwards, with systems earlier in the
+    /// dependency order getting higher priorities.
+    pub fn build(self, planner: &mut Planner<()>, base: Priority) -> Result<(), SystemGraphError> {
+        let priorities = self.priorities(base)?;
+        for (mut node, priority) in self.nodes.into_iter().zip(priorities) {
+            (node.add)(planner, &node.name, priority);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the priority of every node, in order.
+    fn priorities(&self, base: Priority) -> Result<Vec<Priority>, SystemGraphError> {
+        let mut indices = HashMap::default();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if indices.insert(node.name.as_str(), i).is_some() {
+                return Err(SystemGraphError::DuplicateName(node.name.clone()));
+            }
+        }
+
+        let mut dependencies = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let mut node_dependencies = Vec::with_capacity(node.dependencies.len());
+            for dependency in &node.dependencies {
+                match indices.get(dependency.as_str()) {
+                    Some(&index) => node_dependencies.push(index),
+                    None => {
+                        return Err(SystemGraphError::MissingDependency {
+                            system: node.name.clone(),
+                            dependency: dependency.clone(),
+                        })
+                    }
+                }
+            }
+            dependencies.push(node_dependencies);
+        }
+
+        for &(ref before, ref after) in &self.orders {
+            let after_index = match indices.get(after.as_str()) {
+                Some(&index) => index,
+                None => {
+                    return Err(SystemGraphError::MissingDependency {
+                        system: before.clone(),
+                        dependency: after.clone(),
+                    })
+                }
+            };
+            match indices.get(before.as_str()) {
+                Some(&index) => dependencies[after_index].push(index),
+                None => {
+                    return Err(SystemGraphError::MissingDependency {
+                        system: after.clone(),
+                        dependency: before.clone(),
+                    })
+                }
+            }
+        }
+
+        // Depth of every node, i.e. the length of its longest dependency
+        // chain. `None` while the node is being visited.
+        let mut depths: Vec<Option<Option<usize>>> = vec![None; self.nodes.len()];
+        for i in 0..self.nodes.len() {
+            self.depth(i, &dependencies, &mut depths)?;
+        }
+
+        let depths: Vec<usize> = depths.into_iter().map(|d| d.unwrap().unwrap()).collect();
+        let max = depths.iter().cloned().max().unwrap_or(0);
+        Ok(depths.into_iter().map(|d| base + (max - d) as Priority).collect())
+    }
+
+    fn depth(&self,
+             index: usize,
+             dependencies: &[Vec<usize>],
+             depths: &mut Vec<Option<Option<usize>>>)
+             -> Result<usize, SystemGraphError> {
+        match depths[index] {
+            Some(Some(depth)) => return Ok(depth),
+            Some(None) => return Err(SystemGraphError::Cycle(self.nodes[index].name.clone())),
+            None => {}
+        }
+
+        depths[index] = Some(None);
+        let mut depth = 0;
+        for &dependency in &dependencies[index] {
+            depth = depth.max(self.depth(dependency, dependencies, depths)? + 1);
+        }
+        depths[index] = Some(Some(depth));
+
+        Ok(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SystemGraph, SystemGraphError};
+    use ecs::{RunArg, System};
+
+    struct NoopSystem;
+
+    impl System<()> for NoopSystem {
+        fn run(&mut self, arg: RunArg, _: ()) {
+            arg.fetch(|_| ());
+        }
+    }
+
+    #[test]
+    fn order_by_dependencies() {
+        let graph = SystemGraph::new()
+            .with(NoopSystem, "render", &["physics", "input"])
+            .with(NoopSystem, "physics", &["input"])
+            .with(NoopSystem, "input", &[]);
+        assert_eq!(graph.priorities(10).unwrap(), vec![10, 11, 12]);
+    }
+
+    #[test]
+    fn splice_into_order() {
+        let graph = SystemGraph::new()
+            .with(NoopSystem, "input", &[])
+            .with(NoopSystem, "physics", &["input"])
+            .with(NoopSystem, "ai", &["input"])
+            .with_order("ai", "physics");
+        assert_eq!(graph.priorities(0).unwrap(), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn reject_unknown_order_names() {
+        let unknown_after = SystemGraph::new()
+            .with(NoopSystem, "input", &[])
+            .with_order("input", "phisics");
+        assert_eq!(unknown_after.priorities(0),
+                   Err(SystemGraphError::MissingDependency {
+                       system: "input".to_string(),
+                       dependency: "phisics".to_string(),
+                   }));
+
+        let unknown_before = SystemGraph::new()
+            .with(NoopSystem, "physics", &[])
+            .with_order("inptu", "physics");
+        assert_eq!(unknown_before.priorities(0),
+                   Err(SystemGraphError::MissingDependency {
+                       system: "physics".to_string(),
+                       dependency: "inptu".to_string(),
+                   }));
+    }
+
+    #[test]
+    fn reject_invalid_graphs() {
+        let missing = SystemGraph::new().with(NoopSystem, "physics", &["input"]);
+        assert_eq!(missing.priorities(0),
+                   Err(SystemGraphError::MissingDependency {
+                       system: "physics".to_string(),
+                       dependency: "input".to_string(),
+                   }));
+
+        let duplicate = SystemGraph::new()
+            .with(NoopSystem, "input", &[])
+            .with(NoopSystem, "input", &[]);
+        assert_eq!(duplicate.priorities(0),
+                   Err(SystemGraphError::DuplicateName("input".to_string())));
+
+        let cycle = SystemGraph::new()
+            .with(NoopSystem, "a", &["b"])
+            .with(NoopSystem, "b", &["a"]);
+        assert_eq!(cycle.priorities(0), Err(SystemGraphError::Cycle("a".to_string())));
+    }
+}
@@ -0,0 +1,105 @@
+//! Propagating `HiddenPropagate` down the hierarchy.
+
+use fnv::FnvHashSet as HashSet;
+
+use ecs::{Entity, Join, RunArg, System};
+use ecs::components::{Child, Hidden, HiddenPropagate};
+
+/// Adds `Hidden` to all descendants of entities with a `HiddenPropagate`
+/// component, and removes it from descendants which aren't below such an
+/// entity anymore.
+///
+/// Note: `Hidden` components which were added by hand to a descendant of a
+/// hidden entity are removed along with the propagated ones once the
+/// ancestor is shown again.
+#[derive(Default)]
+pub struct HideHierarchySystem {
+    /// Entities which were hidden by this system.
+    hidden: HashSet<Entity>,
+    /// Entities which are hidden in the current update.
+    current: HashSet<Entity>,
+}
+
+impl HideHierarchySystem {
+    /// Creates a new hide hierarchy system.
+    pub fn new() -> HideHierarchySystem {
+        HideHierarchySystem::default()
+    }
+}
+
+impl System<()> for HideHierarchySystem {
+    fn run(&mut self, arg: RunArg, _: ()) {
+        let (entities, children, propagates, mut hiddens) = arg.fetch(|w| {
+            (w.entities(), w.read::<Child>(), w.read::<HiddenPropagate>(), w.write::<Hidden>())
+        });
+
+        let num_children = (&children).iter().count();
+        for (entity, child) in (&entities, &children).iter() {
+            // Walks up the ancestors, giving up after as many steps as there
+            // are children in case of cycles.
+            let mut parent = child.parent();
+            for _ in 0..num_children {
+                if propagates.get(parent).is_some() {
+                    self.current.insert(entity);
+                    break;
+                }
+
+                match children.get(parent) {
+                    Some(child) => parent = child.parent(),
+                    None => break,
+                }
+            }
+        }
+
+        for &entity in &self.current {
+            if hiddens.get(entity).is_none() {
+                hiddens.insert(entity, Hidden);
+            }
+        }
+
+        for &entity in self.hidden.difference(&self.current) {
+            hiddens.remove(entity);
+        }
+
+        ::std::mem::swap(&mut self.hidden, &mut self.current);
+        self.current.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HideHierarchySystem;
+    use ecs::{Planner, World};
+    use ecs::components::{Child, Hidden, HiddenPropagate};
+
+    #[test]
+    fn hide_descendants() {
+        let mut world = World::new();
+        world.register::<Child>();
+        world.register::<Hidden>();
+        world.register::<HiddenPropagate>();
+
+        let root = world.create_now().with(HiddenPropagate).build();
+        let child = world.create_now().with(Child::new(root)).build();
+        let grandchild = world.create_now().with(Child::new(child)).build();
+        let other = world.create_now().build();
+
+        let mut planner = Planner::new(world, 1);
+        planner.add_system(HideHierarchySystem::new(), "hide_hierarchy_system", 0);
+        planner.dispatch(());
+        planner.wait();
+        {
+            let hidden = planner.mut_world().read::<Hidden>();
+            assert!(hidden.get(child).is_some());
+            assert!(hidden.get(grandchild).is_some());
+            assert!(hidden.get(other).is_none());
+        }
+
+        planner.mut_world().write::<HiddenPropagate>().remove(root);
+        planner.dispatch(());
+        planner.wait();
+        let hidden = planner.mut_world().read::<Hidden>();
+        assert!(hidden.get(child).is_none());
+        assert!(hidden.get(grandchild).is_none());
+    }
+}
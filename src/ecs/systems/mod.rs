@@ -1,5 +1,13 @@
 //! Built-in `specs` `System`s.
 
+mod hide_hierarchy;
+mod pausable;
+mod sprite_animation;
+mod timer;
 mod transform;
 
-pub use self::transform::TransformSystem;
+pub use self::hide_hierarchy::HideHierarchySystem;
+pub use self::pausable::{Pausable, PauseSwitch};
+pub use self::sprite_animation::{SpriteAnimationBundle, SpriteAnimationSystem};
+pub use self::timer::{TimerBundle, TimerSystem};
+pub use self::transform::{TransformBundle, TransformSystem};
@@ -0,0 +1,128 @@
+//! Systems which can be paused at runtime.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ecs::{RunArg, System};
+
+/// Shared switch pausing and resuming a `Pausable` system. Cloning the switch
+/// gives another handle to the same system, so it can be kept in a `State` or
+/// added to the world as a resource.
+#[derive(Clone, Default)]
+pub struct PauseSwitch {
+    paused: Arc<AtomicBool>,
+}
+
+impl PauseSwitch {
+    /// Creates a new switch, initially not paused.
+    pub fn new() -> PauseSwitch {
+        PauseSwitch::default()
+    }
+
+    /// Returns `true` if the system is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Pauses or resumes the system.
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+    }
+
+    /// Pauses the system.
+    pub fn pause(&self) {
+        self.set_paused(true);
+    }
+
+    /// Resumes the system.
+    pub fn resume(&self) {
+        self.set_paused(false);
+    }
+}
+
+/// Wraps a system so it's skipped while its `PauseSwitch` is paused, without
+/// rebuilding the planner. Useful e.g. for stopping gameplay systems while a
+/// pause menu is open.
+///
+/// ```ignore
+/// let movement = Pausable::new(MovementSystem);
+/// let switch = movement.switch();
+/// planner.add_system(movement, "movement_system", 1);
+///
+/// // Later, when opening the pause menu:
+/// switch.pause();
+/// ```
+pub struct Pausable<S> {
+    system: S,
+    switch: PauseSwitch,
+}
+
+impl<S> Pausable<S> {
+    /// Wraps `system` with a new switch.
+    pub fn new(system: S) -> Pausable<S> {
+        Pausable::with_switch(system, PauseSwitch::new())
+    }
+
+    /// Wraps `system` with an existing switch, so several systems can be
+    /// paused together.
+    pub fn with_switch(system: S, switch: PauseSwitch) -> Pausable<S> {
+        Pausable {
+            system: system,
+            switch: switch,
+        }
+    }
+
+    /// Returns a handle to the switch of this system.
+    pub fn switch(&self) -> PauseSwitch {
+        self.switch.clone()
+    }
+}
+
+impl<S: System<()>> System<()> for Pausable<S> {
+    fn run(&mut self, arg: RunArg, ctx: ()) {
+        if self.switch.is_paused() {
+            // The planner expects every system to fetch exactly once.
+            arg.fetch(|_| ());
+        } else {
+            self.system.run(arg, ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pausable;
+    use ecs::{Planner, RunArg, System, World};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountSystem(Arc<AtomicUsize>);
+
+    impl System<()> for CountSystem {
+        fn run(&mut self, arg: RunArg, _: ()) {
+            arg.fetch(|_| ());
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn skip_paused_system() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let system = Pausable::new(CountSystem(count.clone()));
+        let switch = system.switch();
+        let mut planner = Planner::new(World::new(), 1);
+        planner.add_system(system, "count_system", 0);
+
+        planner.dispatch(());
+        planner.wait();
+        switch.pause();
+        planner.dispatch(());
+        planner.wait();
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        switch.resume();
+        planner.dispatch(());
+        planner.wait();
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,78 @@
+//! Advancing `SpriteAnimation` components.
+
+use ecs::{Join, Planner, RunArg, System, SystemBundle};
+use ecs::components::{AnimationFinished, SpriteAnimation};
+use ecs::resources::{Broadcaster, Time};
+
+/// Advances every `SpriteAnimation` component by `Time::delta_time` and
+/// publishes an `AnimationFinished` event through the `Broadcaster` resource
+/// for each animation which ended.
+#[derive(Default)]
+pub struct SpriteAnimationSystem;
+
+impl SpriteAnimationSystem {
+    /// Creates a new sprite animation system.
+    pub fn new() -> SpriteAnimationSystem {
+        SpriteAnimationSystem
+    }
+}
+
+impl System<()> for SpriteAnimationSystem {
+    fn run(&mut self, arg: RunArg, _: ()) {
+        let (entities, mut animations, mut broadcaster, time) = arg.fetch(|w| {
+            (w.entities(),
+             w.write::<SpriteAnimation>(),
+             w.write_resource::<Broadcaster>(),
+             w.read_resource::<Time>())
+        });
+
+        for (entity, animation) in (&entities, &mut animations).iter() {
+            if animation.advance(time.delta_time) {
+                broadcaster.publish().with(AnimationFinished { entity: entity }).build();
+            }
+        }
+    }
+}
+
+/// Registers the `SpriteAnimation` component and adds
+/// `SpriteAnimationSystem`.
+pub struct SpriteAnimationBundle;
+
+impl SystemBundle for SpriteAnimationBundle {
+    fn build(self, planner: &mut Planner<()>) {
+        planner.mut_world().register::<SpriteAnimation>();
+        planner.add_system(SpriteAnimationSystem::new(), "sprite_animation_system", 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use ecs::{Join, Planner, SystemBundle, World};
+    use ecs::components::{AnimationFinished, PlayMode, SpriteAnimation};
+    use ecs::resources::{Broadcaster, Time};
+    use super::SpriteAnimationBundle;
+
+    #[test]
+    fn publish_animation_finished() {
+        let mut broadcaster = Broadcaster::new();
+        broadcaster.register::<AnimationFinished>();
+        let mut world = World::new();
+        world.add_resource::<Broadcaster>(broadcaster);
+        world.add_resource::<Time>(Time::default());
+
+        let mut planner = Planner::new(world, 1);
+        SpriteAnimationBundle.build(&mut planner);
+        let animation = SpriteAnimation::new(vec![0, 1], Duration::from_millis(10), PlayMode::Once);
+        let entity = planner.mut_world().create_now().with(animation).build();
+
+        planner.mut_world().write_resource::<Time>().advance_frame(Duration::from_millis(20));
+        planner.dispatch(());
+        planner.wait();
+
+        let broadcaster = planner.mut_world().read_resource::<Broadcaster>();
+        let events = broadcaster.read::<AnimationFinished>();
+        let events: Vec<_> = events.iter().map(|e| e.entity).collect();
+        assert_eq!(events, vec![entity]);
+    }
+}
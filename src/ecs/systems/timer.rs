@@ -0,0 +1,50 @@
+//! Ticking `Timer` components.
+
+use ecs::{Join, Planner, RunArg, System, SystemBundle};
+use ecs::components::{Timer, TimerEvent};
+use ecs::resources::{Broadcaster, Time};
+
+/// Advances every `Timer` component by `Time::delta_time` and publishes a
+/// `TimerEvent` through the `Broadcaster` resource for each timer which
+/// expired.
+#[derive(Default)]
+pub struct TimerSystem;
+
+impl TimerSystem {
+    /// Creates a new timer system.
+    pub fn new() -> TimerSystem {
+        TimerSystem
+    }
+}
+
+impl System<()> for TimerSystem {
+    fn run(&mut self, arg: RunArg, _: ()) {
+        let (entities, mut timers, mut broadcaster, time) = arg.fetch(|w| {
+            (w.entities(),
+             w.write::<Timer>(),
+             w.write_resource::<Broadcaster>(),
+             w.read_resource::<Time>())
+        });
+
+        for (entity, timer) in (&entities, &mut timers).iter() {
+            let count = timer.tick(time.delta_time);
+            if count > 0 {
+                let event = TimerEvent {
+                    entity: entity,
+                    count: count,
+                };
+                broadcaster.publish().with(event).build();
+            }
+        }
+    }
+}
+
+/// Registers the `Timer` component and adds `TimerSystem`.
+pub struct TimerBundle;
+
+impl SystemBundle for TimerBundle {
+    fn build(self, planner: &mut Planner<()>) {
+        planner.mut_world().register::<Timer>();
+        planner.add_system(TimerSystem::new(), "timer_system", 0);
+    }
+}
@@ -3,8 +3,9 @@
 use cgmath::Matrix4;
 use fnv::{FnvHashMap as HashMap, FnvHashSet as HashSet};
 
-use ecs::{Join, Entity, RunArg, System};
-use ecs::components::{LocalTransform, Transform, Child, Init};
+use ecs::{Join, Entity, Planner, RunArg, System, SystemBundle};
+use ecs::components::{LocalTransform, Transform, Child, Hidden, HiddenPropagate, Init};
+use ecs::systems::HideHierarchySystem;
 
 /// Handles updating `Transform` components based on the `LocalTransform`
 /// component and parents.
@@ -166,9 +167,30 @@ impl System<()> for TransformSystem {
     }
 }
 
+/// Registers the transform and visibility components and adds
+/// `TransformSystem` and `HideHierarchySystem`.
+pub struct TransformBundle;
+
+impl SystemBundle for TransformBundle {
+    fn build(self, planner: &mut Planner<()>) {
+        {
+            let world = planner.mut_world();
+            world.register::<Child>();
+            world.register::<Hidden>();
+            world.register::<HiddenPropagate>();
+            world.register::<Init>();
+            world.register::<LocalTransform>();
+            world.register::<Transform>();
+        }
+        planner.add_system(TransformSystem::new(), "transform_system", 0);
+        planner.add_system(HideHierarchySystem::new(), "hide_hierarchy_system", 0);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use cgmath::{Decomposed, Quaternion, Vector3, Matrix4};
+    use Float;
     use ecs::components::{LocalTransform, Transform};
 
     #[test]
@@ -185,8 +207,8 @@ mod tests {
         };
 
         let matrix = transform.matrix();
-        let cg_matrix: Matrix4<f32> = decomposed.into();
-        let cg_matrix: [[f32; 4]; 4] = cg_matrix.into();
+        let cg_matrix: Matrix4<Float> = decomposed.into();
+        let cg_matrix: [[Float; 4]; 4] = cg_matrix.into();
 
         assert_eq!(matrix, cg_matrix);
     }
@@ -194,7 +216,7 @@ mod tests {
     #[test]
     fn into_from() {
         let transform = Transform::default();
-        let primitive: [[f32; 4]; 4] = transform.into();
+        let primitive: [[Float; 4]; 4] = transform.into();
         assert_eq!(primitive, transform.0);
 
         let transform: Transform = primitive.into();
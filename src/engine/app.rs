@@ -2,20 +2,29 @@
 
 #[cfg(feature="profiler")]
 use thread_profiler::{register_thread_with_profiler, write_profile};
+use fnv::FnvHashSet as HashSet;
 use num_cpus;
+use std::any::{Any, TypeId};
 use std::time::{Duration, Instant};
 
 use asset_manager::AssetManager;
-use ecs::{Component, Planner, Priority, System, World};
-use ecs::components::{LocalTransform, Transform, Child, Init, Renderable};
-use ecs::resources::Time;
-use ecs::systems::TransformSystem;
-use engine::state::{State, StateMachine};
+use ecs::{Component, Planner, Priority, System, SystemBundle, SystemGraph, SystemGraphError,
+          World};
+use ecs::components::{AnimationFinished, Renderable, TimerEvent};
+use ecs::resources::{Broadcaster, FrameMetrics, FrameTimings, Time};
+use ecs::systems::{SpriteAnimationBundle, TimerBundle, TransformBundle};
+use engine::event::UserEvents;
+use engine::frame_limiter::{FrameLimiter, FrameRateLimitStrategy};
+use engine::state::{State, StateMachine, TransQueue};
 use engine::timing::Stopwatch;
 use gfx_device;
 use gfx_device::{DisplayConfig, GfxDevice, gfx_types};
 use renderer::{AmbientLight, DirectionalLight, Pipeline, PointLight, target};
 
+/// Callback run by `Application` when shutting down, see
+/// `Application::add_shutdown_hook`.
+pub type ShutdownHook = Box<FnMut(&mut World, &mut AssetManager)>;
+
 /// User-friendly facade for building games. Manages main loop.
 pub struct Application {
     // Graphics and asset management structs.
@@ -27,8 +36,8 @@ pub struct Application {
 
     // State management and game loop timing structs.
     delta_time: Duration,
-    fixed_step: Duration,
-    last_fixed_update: Instant,
+    frame_limiter: FrameLimiter,
+    shutdown_hooks: Vec<ShutdownHook>,
     states: StateMachine,
     timer: Stopwatch,
 }
@@ -36,10 +45,23 @@ pub struct Application {
 impl Application {
     /// Creates a new Application with the given initial game state, planner,
     /// and display configuration.
-    pub fn new<T>(initial_state: T, mut planner: Planner<()>, cfg: DisplayConfig) -> Application
+    pub fn new<T>(initial_state: T, planner: Planner<()>, cfg: DisplayConfig) -> Application
         where T: State + 'static
     {
-        use ecs::resources::{Camera, Projection, ScreenDimensions};
+        Application::with_built_bundles(initial_state, planner, cfg, &HashSet::default())
+    }
+
+    /// Creates a new Application, skipping the built-in bundles whose types
+    /// are in `built` because they were added to `planner` already.
+    fn with_built_bundles<T>(initial_state: T,
+                             mut planner: Planner<()>,
+                             cfg: DisplayConfig,
+                             built: &HashSet<TypeId>)
+                             -> Application
+        where T: State + 'static
+    {
+        use ecs::components;
+        use ecs::resources::{ActiveCamera, Camera, Projection, ScreenDimensions};
 
         #[cfg(feature="profiler")]
         register_thread_with_profiler("Main".into());
@@ -57,19 +79,22 @@ impl Application {
         let geom_buf = target::GeometryBuffer::new(&mut factory, (w as u16, h as u16));
         pipe.targets.insert("gbuffer".into(), Box::new(geom_buf));
 
+        let strategy = if cfg.vsync {
+            FrameRateLimitStrategy::Vsync
+        } else {
+            FrameRateLimitStrategy::Unlimited
+        };
+        let frame_limiter = FrameLimiter::new(strategy, 144);
+
         let mut assets = AssetManager::new();
         assets.add_loader::<gfx_types::Factory>(factory);
 
-        let trans_sys = TransformSystem::new();
-        planner.add_system::<TransformSystem>(trans_sys, "transform_system", 0);
+        build_default_bundle(TransformBundle, &mut planner, built);
+        build_default_bundle(TimerBundle, &mut planner, built);
+        build_default_bundle(SpriteAnimationBundle, &mut planner, built);
 
         {
             let mut world = planner.mut_world();
-            let time = Time {
-                delta_time: Duration::new(0, 0),
-                fixed_step: Duration::new(0, 16666666),
-                last_fixed_update: Instant::now(),
-            };
             if let Some((w, h)) = device.get_dimensions() {
                 let dim = ScreenDimensions::new(w, h);
                 let proj = Projection::Perspective {
@@ -86,15 +111,21 @@ impl Application {
                 world.add_resource::<Camera>(camera);
             }
 
+            let mut broadcaster = Broadcaster::new();
+            broadcaster.register::<AnimationFinished>();
+            broadcaster.register::<TimerEvent>();
+
+            world.add_resource::<ActiveCamera>(ActiveCamera::default());
             world.add_resource::<AmbientLight>(AmbientLight::default());
-            world.add_resource::<Time>(time);
-            world.register::<Child>();
+            world.add_resource::<Broadcaster>(broadcaster);
+            world.add_resource::<FrameMetrics>(FrameMetrics::default());
+            world.add_resource::<Time>(Time::default());
+            world.add_resource::<TransQueue>(TransQueue::new());
+            world.add_resource::<UserEvents>(UserEvents::new());
+            world.register::<components::CameraView>();
             world.register::<DirectionalLight>();
-            world.register::<Init>();
-            world.register::<LocalTransform>();
             world.register::<PointLight>();
             world.register::<Renderable>();
-            world.register::<Transform>();
         }
 
         Application {
@@ -105,8 +136,8 @@ impl Application {
             planner: planner,
             timer: Stopwatch::new(),
             delta_time: Duration::new(0, 0),
-            fixed_step: Duration::new(0, 16666666),
-            last_fixed_update: Instant::now(),
+            frame_limiter: frame_limiter,
+            shutdown_hooks: Vec::new(),
         }
     }
 
@@ -117,6 +148,22 @@ impl Application {
         ApplicationBuilder::new(initial_state, cfg)
     }
 
+    /// Sets how the main loop limits its frame rate. Defaults to following
+    /// vsync if `DisplayConfig::vsync` is enabled, and unlimited otherwise.
+    pub fn set_frame_limit(&mut self, strategy: FrameRateLimitStrategy, fps: u32) {
+        self.frame_limiter.set_rate(strategy, fps);
+    }
+
+    /// Adds a callback which is run after the last state has stopped, e.g.
+    /// for flushing saves or closing network connections. Hooks run in the
+    /// reverse order they were added, before the world and assets are
+    /// dropped.
+    pub fn add_shutdown_hook<F>(&mut self, hook: F)
+        where F: FnMut(&mut World, &mut AssetManager) + 'static
+    {
+        self.shutdown_hooks.push(Box::new(hook));
+    }
+
     /// Starts the application and manages the game loop.
     pub fn run(&mut self) {
         {
@@ -125,9 +172,11 @@ impl Application {
             self.initialize();
         }
 
+        self.frame_limiter.start();
         while self.states.is_running() {
             self.timer.restart();
             self.advance_frame();
+            self.frame_limiter.wait();
             self.timer.stop();
             self.delta_time = self.timer.elapsed();
         }
@@ -152,9 +201,16 @@ impl Application {
     /// Advances the game world by one tick.
     fn advance_frame(&mut self) {
         use ecs::resources::ScreenDimensions;
+        let mut timings = FrameTimings::default();
+        {
+            let world = &mut self.planner.mut_world();
+            world.write_resource::<Time>().advance_frame(self.delta_time);
+        }
+
         {
             #[cfg(feature="profiler")]
             profile_scope!("handle_events");
+            let start = Instant::now();
             let events = self.gfx_device.poll_events();
             let world = &mut self.planner.mut_world();
             let assets = &mut self.assets;
@@ -162,47 +218,64 @@ impl Application {
 
             self.states.handle_events(events.as_ref(), world, assets, pipe);
 
+            let user_events = world.write_resource::<UserEvents>().drain();
+            if !user_events.is_empty() {
+                self.states.handle_user_events(&user_events, world, assets, pipe);
+            }
+            timings.handle_events = start.elapsed();
+
             #[cfg(feature="profiler")]
             profile_scope!("fixed_update");
-            if self.last_fixed_update.elapsed() >= self.fixed_step {
+            let start = Instant::now();
+            while world.write_resource::<Time>().step_fixed_update() {
                 self.states.fixed_update(world, assets, pipe);
-                self.last_fixed_update += self.fixed_step;
+                timings.fixed_updates += 1;
             }
+            timings.fixed_update = start.elapsed();
 
             #[cfg(feature="profiler")]
             profile_scope!("update");
+            let start = Instant::now();
             self.states.update(world, assets, pipe);
+
+            let queued = world.write_resource::<TransQueue>().drain();
+            for trans in queued {
+                self.states.transition(trans, world, assets, pipe);
+            }
+            timings.update = start.elapsed();
         }
 
         #[cfg(feature="profiler")]
         profile_scope!("dispatch");
-        self.planner.dispatch(());
-        self.planner.wait();
+        let start = Instant::now();
+        dispatch_systems(&mut self.planner);
+        timings.dispatch = start.elapsed();
 
         #[cfg(feature="profiler")]
         profile_scope!("render_world");
         {
+            let start = Instant::now();
             let world = &mut self.planner.mut_world();
             if let Some((w, h)) = self.gfx_device.get_dimensions() {
                 let mut dim = world.write_resource::<ScreenDimensions>();
                 dim.update(w, h);
             }
 
-            {
-                let mut time = world.write_resource::<Time>();
-                time.delta_time = self.delta_time;
-                time.fixed_step = self.fixed_step;
-                time.last_fixed_update = self.last_fixed_update;
-            }
-
             let pipe = &mut self.pipe;
             self.gfx_device.render_world(world, pipe);
+            timings.render = start.elapsed();
+
+            world.write_resource::<FrameMetrics>().record(timings);
         }
     }
 
     /// Cleans up after the quit signal is received.
     fn shutdown(&mut self) {
-        // Placeholder.
+        let world = &mut self.planner.mut_world();
+        let assets = &mut self.assets;
+        while let Some(mut hook) = self.shutdown_hooks.pop() {
+            hook(world, assets);
+        }
     }
 
     #[cfg(feature="profiler")]
@@ -219,7 +292,10 @@ impl Application {
 pub struct ApplicationBuilder<T>
     where T: State + 'static
 {
+    bundles: HashSet<TypeId>,
     config: DisplayConfig,
+    frame_limit: Option<(FrameRateLimitStrategy, u32)>,
+    shutdown_hooks: Vec<ShutdownHook>,
     initial_state: T,
     planner: Planner<()>,
 }
@@ -231,7 +307,10 @@ impl<T> ApplicationBuilder<T>
     /// display configuration.
     pub fn new(initial_state: T, cfg: DisplayConfig) -> ApplicationBuilder<T> {
         ApplicationBuilder {
+            bundles: HashSet::default(),
             config: cfg,
+            frame_limit: None,
+            shutdown_hooks: Vec::new(),
             initial_state: initial_state,
             planner: Planner::new(World::new(), num_cpus::get()),
         }
@@ -257,8 +336,117 @@ impl<T> ApplicationBuilder<T>
         self
     }
 
+    /// Limits the frame rate of the main loop to `fps` frames per second
+    /// using the given strategy.
+    pub fn with_frame_limit(mut self, strategy: FrameRateLimitStrategy, fps: u32) -> ApplicationBuilder<T> {
+        self.frame_limit = Some((strategy, fps));
+        self
+    }
+
+    /// Adds the systems of `graph` ordered by their dependencies, see
+    /// `SystemGraph`. The systems get priorities from 1 upwards, so they are
+    /// dispatched before the built-in systems.
+    pub fn with_system_graph(mut self, graph: SystemGraph) -> Result<ApplicationBuilder<T>, SystemGraphError> {
+        graph.build(&mut self.planner, 1)?;
+        Ok(self)
+    }
+
+    /// Registers the systems, component types, and resources of a bundle.
+    ///
+    /// Bundles are built right away, before `Application` adds its default
+    /// resources, so they must not rely on those being present. Adding a
+    /// bundle type a second time does nothing, and the built-in
+    /// `TransformBundle`, `TimerBundle`, and `SpriteAnimationBundle` are only
+    /// added by `Application` if they weren't added here.
+    pub fn with_bundle<B>(mut self, bundle: B) -> ApplicationBuilder<T>
+        where B: SystemBundle + Any
+    {
+        if self.bundles.insert(TypeId::of::<B>()) {
+            bundle.build(&mut self.planner);
+        }
+        self
+    }
+
+    /// Adds a callback which is run when the application shuts down, see
+    /// `Application::add_shutdown_hook`.
+    pub fn with_shutdown_hook<F>(mut self, hook: F) -> ApplicationBuilder<T>
+        where F: FnMut(&mut World, &mut AssetManager) + 'static
+    {
+        self.shutdown_hooks.push(Box::new(hook));
+        self
+    }
+
     /// Builds the Application and returns the result.
     pub fn done(self) -> Application {
-        Application::new(self.initial_state, self.planner, self.config)
+        let mut app = Application::with_built_bundles(self.initial_state,
+                                                      self.planner,
+                                                      self.config,
+                                                      &self.bundles);
+        if let Some((strategy, fps)) = self.frame_limit {
+            app.set_frame_limit(strategy, fps);
+        }
+        app.shutdown_hooks = self.shutdown_hooks;
+        app
+    }
+}
+
+/// Builds one of the bundles `Application` adds by default, unless a bundle
+/// of the same type was built already.
+fn build_default_bundle<B>(bundle: B, planner: &mut Planner<()>, built: &HashSet<TypeId>)
+    where B: SystemBundle + Any
+{
+    if !built.contains(&TypeId::of::<B>()) {
+        bundle.build(planner);
+    }
+}
+
+/// Runs all systems once. Events published through the `Broadcaster` during
+/// the previous dispatch are cleaned right before, so states can read them
+/// while handling events and updating in between two dispatches.
+fn dispatch_systems(planner: &mut Planner<()>) {
+    planner.mut_world().write_resource::<Broadcaster>().clean();
+    planner.dispatch(());
+    planner.wait();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dispatch_systems;
+    use std::time::Duration;
+    use ecs::{Join, Planner, SystemBundle, World};
+    use ecs::components::{Timer, TimerEvent};
+    use ecs::resources::{Broadcaster, Time};
+    use ecs::systems::TimerBundle;
+
+    #[test]
+    fn events_outlive_dispatch() {
+        let mut broadcaster = Broadcaster::new();
+        broadcaster.register::<TimerEvent>();
+        let mut world = World::new();
+        world.add_resource::<Broadcaster>(broadcaster);
+        world.add_resource::<Time>(Time::default());
+
+        let mut planner = Planner::new(world, 1);
+        TimerBundle.build(&mut planner);
+        let entity = planner.mut_world()
+            .create_now()
+            .with(Timer::new(Duration::from_millis(10)))
+            .build();
+
+        planner.mut_world().write_resource::<Time>().advance_frame(Duration::from_millis(20));
+        dispatch_systems(&mut planner);
+
+        // The next frame starts and states handle events and update.
+        planner.mut_world().write_resource::<Time>().advance_frame(Duration::from_millis(20));
+        {
+            let broadcaster = planner.mut_world().read_resource::<Broadcaster>();
+            let events = broadcaster.read::<TimerEvent>();
+            let events: Vec<_> = events.iter().map(|e| e.entity).collect();
+            assert_eq!(events, vec![entity]);
+        }
+
+        dispatch_systems(&mut planner);
+        let broadcaster = planner.mut_world().read_resource::<Broadcaster>();
+        assert_eq!(broadcaster.read::<TimerEvent>().iter().count(), 0);
     }
 }
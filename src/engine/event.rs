@@ -1,7 +1,9 @@
-//! This module contains the `WindowEvent` type and re-exports glutin event
-//! types.
+//! This module contains the `WindowEvent` and `UserEvent` types and
+//! re-exports glutin event types.
 
 use ecs::{Component, VecStorage};
+use std::any::Any;
+use std::mem;
 use std::ops::{Deref, DerefMut};
 
 pub use glutin::{Event, ElementState, ScanCode, VirtualKeyCode, MouseScrollDelta, TouchPhase,
@@ -37,3 +39,57 @@ impl DerefMut for WindowEvent {
         &mut self.payload
     }
 }
+
+/// A game-defined event, e.g. "match ended", posted through the `UserEvents`
+/// resource and passed to `State::handle_user_events`.
+pub struct UserEvent {
+    payload: Box<Any + Send + Sync>,
+}
+
+impl UserEvent {
+    /// Wraps a game-defined event.
+    pub fn new<E: Any + Send + Sync>(event: E) -> UserEvent {
+        UserEvent { payload: Box::new(event) }
+    }
+
+    /// Returns `true` if the event is of type `E`.
+    pub fn is<E: Any>(&self) -> bool {
+        self.downcast_ref::<E>().is_some()
+    }
+
+    /// Returns the event if it is of type `E`.
+    pub fn downcast_ref<E: Any>(&self) -> Option<&E> {
+        let payload: &Any = &*self.payload;
+        payload.downcast_ref()
+    }
+}
+
+/// Queue of `UserEvent`s, added to `ecs::World` as a resource by default.
+/// Systems and states push events into it, and `Application` passes them to
+/// the active state at the start of the next frame.
+#[derive(Default)]
+pub struct UserEvents {
+    events: Vec<UserEvent>,
+}
+
+impl UserEvents {
+    /// Creates an empty queue.
+    pub fn new() -> UserEvents {
+        UserEvents::default()
+    }
+
+    /// Posts a game-defined event.
+    pub fn push<E: Any + Send + Sync>(&mut self, event: E) {
+        self.events.push(UserEvent::new(event));
+    }
+
+    /// Returns `true` if no events are queued.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Removes and returns all queued events.
+    pub fn drain(&mut self) -> Vec<UserEvent> {
+        mem::replace(&mut self.events, Vec::new())
+    }
+}
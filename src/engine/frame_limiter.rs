@@ -0,0 +1,130 @@
+//! Limiting the frame rate of the main loop.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How `FrameLimiter` waits for the end of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameRateLimitStrategy {
+    /// Don't wait at all, running as many frames as possible.
+    Unlimited,
+    /// Don't wait, relying on vertical synchronization of the display to
+    /// limit the frame rate. Requires `DisplayConfig::vsync`.
+    Vsync,
+    /// Put the thread to sleep for the rest of the frame. This is the most
+    /// power-efficient strategy, but the accuracy of `thread::sleep` varies
+    /// between platforms.
+    Sleep,
+    /// Sleep until the given `Duration` is left of the frame, then yield the
+    /// thread until the frame is over. More accurate than `Sleep` at the cost
+    /// of some CPU time.
+    SleepAndYield(Duration),
+}
+
+impl Default for FrameRateLimitStrategy {
+    fn default() -> FrameRateLimitStrategy {
+        FrameRateLimitStrategy::Unlimited
+    }
+}
+
+/// Waits at the end of every frame to keep the main loop at a target frame
+/// rate, so that e.g. menus don't run at thousands of frames per second.
+pub struct FrameLimiter {
+    frame_duration: Duration,
+    last_call: Instant,
+    strategy: FrameRateLimitStrategy,
+}
+
+impl Default for FrameLimiter {
+    fn default() -> FrameLimiter {
+        FrameLimiter::new(FrameRateLimitStrategy::default(), 144)
+    }
+}
+
+impl FrameLimiter {
+    /// Creates a new frame limiter targeting `fps` frames per second.
+    pub fn new(strategy: FrameRateLimitStrategy, fps: u32) -> FrameLimiter {
+        let mut limiter = FrameLimiter {
+            frame_duration: Duration::new(0, 0),
+            last_call: Instant::now(),
+            strategy: strategy,
+        };
+        limiter.set_rate(strategy, fps);
+        limiter
+    }
+
+    /// Changes the strategy and the target frame rate. A frame rate of 0
+    /// disables waiting.
+    pub fn set_rate(&mut self, strategy: FrameRateLimitStrategy, fps: u32) {
+        self.strategy = strategy;
+        self.frame_duration = if fps == 0 {
+            Duration::new(0, 0)
+        } else {
+            Duration::new(0, 1_000_000_000 / fps)
+        };
+    }
+
+    /// Returns the current strategy.
+    pub fn strategy(&self) -> FrameRateLimitStrategy {
+        self.strategy
+    }
+
+    /// Marks the start of the first frame.
+    pub fn start(&mut self) {
+        self.last_call = Instant::now();
+    }
+
+    /// Waits until the current frame has taken up its share of time according
+    /// to the strategy, then marks the start of the next frame.
+    pub fn wait(&mut self) {
+        match self.strategy {
+            FrameRateLimitStrategy::Unlimited |
+            FrameRateLimitStrategy::Vsync => {}
+            FrameRateLimitStrategy::Sleep => self.sleep_until(Duration::new(0, 0)),
+            FrameRateLimitStrategy::SleepAndYield(yield_time) => {
+                self.sleep_until(yield_time);
+                while self.last_call.elapsed() < self.frame_duration {
+                    thread::yield_now();
+                }
+            }
+        }
+
+        self.last_call = Instant::now();
+    }
+
+    /// Sleeps until `left` of the frame remains.
+    fn sleep_until(&self, left: Duration) {
+        let elapsed = self.last_call.elapsed();
+        if elapsed + left < self.frame_duration {
+            thread::sleep(self.frame_duration - left - elapsed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameLimiter, FrameRateLimitStrategy};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn limit_frame_rate() {
+        const FRAMES: u32 = 10;
+        let strategies = [FrameRateLimitStrategy::Sleep,
+                          FrameRateLimitStrategy::SleepAndYield(Duration::from_millis(2))];
+
+        for strategy in strategies.iter() {
+            let mut limiter = FrameLimiter::new(*strategy, 100);
+            let start = Instant::now();
+            limiter.start();
+            for _ in 0..FRAMES {
+                limiter.wait();
+            }
+
+            // 10 frames at 100 fps can't take less than 100 ms.
+            let elapsed = start.elapsed();
+            assert!(elapsed >= Duration::from_millis(100),
+                    "{:?} took {:?} for {} frames",
+                    strategy, elapsed, FRAMES);
+        }
+    }
+}
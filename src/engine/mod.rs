@@ -2,10 +2,12 @@
 
 mod app;
 mod event;
+mod frame_limiter;
 mod state;
 mod timing;
 
-pub use self::app::{Application, ApplicationBuilder};
+pub use self::app::{Application, ApplicationBuilder, ShutdownHook};
 pub use self::event::*;
-pub use self::state::{State, StateMachine, Trans};
+pub use self::frame_limiter::{FrameLimiter, FrameRateLimitStrategy};
+pub use self::state::{State, StateMachine, Trans, TransQueue};
 pub use self::timing::Stopwatch;
@@ -1,7 +1,9 @@
 //! Utilities for game state management.
 
+use std::mem;
+
 use asset_manager::AssetManager;
-use engine::event::WindowEvent;
+use engine::event::{UserEvent, WindowEvent};
 use renderer::Pipeline;
 use ecs::World;
 
@@ -20,6 +22,52 @@ pub enum Trans {
     Quit,
 }
 
+/// Queue of state transitions, added to `ecs::World` as a resource by default.
+/// Lets systems drive the application flow, e.g. quitting or switching to a
+/// game over screen. `Application` applies the queued transitions in order
+/// after `State::update`.
+///
+/// Since resources have to be `Send` and `Sync` but states don't, transitions
+/// are queued as functions creating them, which are called on the main
+/// thread.
+///
+/// ```ignore
+/// trans_queue.push(|| Trans::Switch(Box::new(GameOver)));
+/// ```
+#[derive(Default)]
+pub struct TransQueue {
+    queue: Vec<Box<Fn() -> Trans + Send + Sync>>,
+}
+
+impl TransQueue {
+    /// Creates an empty queue.
+    pub fn new() -> TransQueue {
+        TransQueue::default()
+    }
+
+    /// Queues the transition created by `trans`.
+    pub fn push<F>(&mut self, trans: F)
+        where F: Fn() -> Trans + Send + Sync + 'static
+    {
+        self.queue.push(Box::new(trans));
+    }
+
+    /// Queues shutting down the application.
+    pub fn quit(&mut self) {
+        self.push(|| Trans::Quit);
+    }
+
+    /// Returns `true` if no transitions are queued.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Removes all queued transitions and creates them, in order.
+    pub fn drain(&mut self) -> Vec<Trans> {
+        mem::replace(&mut self.queue, Vec::new()).iter().map(|trans| trans()).collect()
+    }
+}
+
 /// A trait which defines game states that can be used by the state machine.
 pub trait State {
     /// Executed when the game state begins.
@@ -44,6 +92,18 @@ pub trait State {
         Trans::None
     }
 
+    /// Executed on every frame with game-defined events posted through the
+    /// `UserEvents` resource since the last frame, right after
+    /// `handle_events`.
+    fn handle_user_events(&mut self,
+                          _events: &[UserEvent],
+                          _world: &mut World,
+                          _assets: &mut AssetManager,
+                          _pipe: &mut Pipeline)
+                          -> Trans {
+        Trans::None
+    }
+
     /// Executed repeatedly at stable, predictable intervals (1/60th of a second
     /// by default).
     fn fixed_update(&mut self,
@@ -114,6 +174,22 @@ impl StateMachine {
         }
     }
 
+    /// Passes game-defined events to the active state to handle.
+    pub fn handle_user_events(&mut self,
+                              events: &[UserEvent],
+                              world: &mut World,
+                              assets: &mut AssetManager,
+                              pipe: &mut Pipeline) {
+        if self.running {
+            let trans = match self.state_stack.last_mut() {
+                Some(state) => state.handle_user_events(events, world, assets, pipe),
+                None => Trans::None,
+            };
+
+            self.transition(trans, world, assets, pipe);
+        }
+    }
+
     /// Updates the currently active state at a steady, fixed interval.
     pub fn fixed_update(&mut self,
                         world: &mut World,
@@ -142,12 +218,12 @@ impl StateMachine {
     }
 
     /// Performs a state transition, if requested by either update() or
-    /// fixed_update().
-    fn transition(&mut self,
-                  request: Trans,
-                  world: &mut World,
-                  assets: &mut AssetManager,
-                  pipe: &mut Pipeline) {
+    /// fixed_update(), or queued in `TransQueue`.
+    pub fn transition(&mut self,
+                      request: Trans,
+                      world: &mut World,
+                      assets: &mut AssetManager,
+                      pipe: &mut Pipeline) {
         if self.running {
             match request {
                 Trans::None => (),
@@ -227,6 +303,8 @@ mod tests {
 
     struct State1(u8);
     struct State2;
+    struct MatchEnded;
+    struct MatchState;
 
     impl State for State1 {
         fn update(&mut self, _: &mut World, _: &mut AssetManager, _: &mut Pipeline) -> Trans {
@@ -245,6 +323,21 @@ mod tests {
         }
     }
 
+    impl State for MatchState {
+        fn handle_user_events(&mut self,
+                              events: &[UserEvent],
+                              _: &mut World,
+                              _: &mut AssetManager,
+                              _: &mut Pipeline)
+                              -> Trans {
+            if events.iter().any(|e| e.is::<MatchEnded>()) {
+                Trans::Quit
+            } else {
+                Trans::None
+            }
+        }
+    }
+
     #[test]
     fn switch_pop() {
         let mut assets = AssetManager::new();
@@ -262,4 +355,41 @@ mod tests {
         sm.update(&mut world, &mut assets, &mut pipe);
         assert!(!sm.is_running());
     }
+
+    #[test]
+    fn user_events() {
+        let mut assets = AssetManager::new();
+        let mut pipe = Pipeline::new();
+        let mut world = World::new();
+
+        let mut sm = StateMachine::new(MatchState);
+        sm.start(&mut world, &mut assets, &mut pipe);
+
+        sm.handle_user_events(&[UserEvent::new(42u32)], &mut world, &mut assets, &mut pipe);
+        assert!(sm.is_running());
+
+        sm.handle_user_events(&[UserEvent::new(MatchEnded)], &mut world, &mut assets, &mut pipe);
+        assert!(!sm.is_running());
+    }
+
+    #[test]
+    fn queued_transitions() {
+        let mut assets = AssetManager::new();
+        let mut pipe = Pipeline::new();
+        let mut world = World::new();
+
+        let mut sm = StateMachine::new(State1(7));
+        sm.start(&mut world, &mut assets, &mut pipe);
+
+        let mut queue = TransQueue::new();
+        queue.push(|| Trans::Push(Box::new(State1(7))));
+        queue.quit();
+        for trans in queue.drain() {
+            assert!(sm.is_running());
+            sm.transition(trans, &mut world, &mut assets, &mut pipe);
+        }
+
+        assert!(!sm.is_running());
+        assert!(queue.is_empty());
+    }
 }
@@ -30,34 +30,43 @@ impl GfxDevice {
 
     /// Render all `Entity`s with `Renderable` components in `World`.
     pub fn render_world(&mut self, world: &mut World, pipe: &Pipeline) {
-        use ecs::components::{Renderable, Transform};
-        use ecs::resources::Projection;
+        use cgmath::{Matrix4, SquareMatrix};
+        use ecs::components::{self, Hidden, HiddenPropagate, Renderable, Transform};
+        use ecs::resources::ActiveCamera;
         use renderer::{AmbientLight, Camera, DirectionalLight, PointLight};
 
-        let camera = world.read_resource::<resources::Camera>();
-        let proj_mat = match camera.proj {
-            Projection::Perspective { fov, aspect_ratio, near, far } => {
-                Camera::perspective(fov, aspect_ratio, near, far)
+        let entities = world.entities();
+        let renderables = world.read::<Renderable>();
+        let global_transforms = world.read::<Transform>();
+
+        // Use the active camera entity if there is one, and the camera
+        // resource otherwise.
+        let active = world.read_resource::<ActiveCamera>().entity.and_then(|entity| {
+            let cameras = world.read::<components::CameraView>();
+            match (cameras.get(entity), global_transforms.get(entity)) {
+                (Some(camera), Some(transform)) => {
+                    Matrix4::from(transform.render_matrix())
+                        .invert()
+                        .map(|view| Camera::new(camera.proj.matrix(), view.into()))
+                }
+                _ => None,
             }
-            Projection::Orthographic { left, right, bottom, top, near, far } => {
-                Camera::orthographic(left, right, bottom, top, near, far)
+        });
+        let camera = match active {
+            Some(camera) => camera,
+            None => {
+                let camera = world.read_resource::<resources::Camera>();
+                let view_mat = Camera::look_at(camera.eye, camera.target, camera.up);
+                Camera::new(camera.proj.matrix(), view_mat)
             }
         };
-
-        let eye = camera.eye;
-        let target = camera.target;
-        let up = camera.up;
-        let view_mat = Camera::look_at(eye, target, up);
-        let camera = Camera::new(proj_mat, view_mat);
         let mut scene = Scene::<Resources>::new(camera);
+        let hidden = world.read::<Hidden>();
+        let hidden_propagate = world.read::<HiddenPropagate>();
 
-        let entities = world.entities();
-        let renderables = world.read::<Renderable>();
-        let global_transforms = world.read::<Transform>();
-
-        // Add all entities with `Renderable` components attached to them to
-        // the scene.
-        for (rend, entity) in (&renderables, &entities).iter() {
+        // Add all visible entities with `Renderable` components attached to
+        // them to the scene.
+        for (rend, entity, _, _) in (&renderables, &entities, !&hidden, !&hidden_propagate).iter() {
             let global_trans = match global_transforms.get(entity) {
                 Some(gt) => *gt,
                 None => Transform::default(),
@@ -86,7 +95,7 @@ impl GfxDevice {
                              -> Option<Fragment<Resources>> {
             let mesh = &rend.mesh;
             Some(Fragment {
-                transform: global_trans.clone().into(),
+                transform: global_trans.render_matrix(),
                 buffer: mesh.buffer.clone(),
                 slice: mesh.slice.clone(),
                 ka: (&rend.ambient).clone(),
@@ -79,8 +79,31 @@ extern crate glutin;
 extern crate genmesh;
 extern crate imagefmt;
 extern crate num_cpus;
+extern crate serde;
 extern crate specs;
 extern crate wavefront_obj;
+#[cfg(target_os="android")]
+extern crate android_glue;
+#[cfg(feature="bincode")]
+extern crate bincode;
+#[cfg(feature="hyper")]
+extern crate hyper;
+#[cfg(feature="lz4")]
+extern crate lz4;
+#[cfg(feature="ring")]
+extern crate ring;
+#[cfg(feature="ron")]
+extern crate ron;
+#[cfg(feature="serde_json")]
+extern crate serde_json;
+#[cfg(feature="tiled")]
+extern crate tiled;
+#[cfg(feature="toml")]
+extern crate toml;
+#[cfg(feature="zip")]
+extern crate zip;
+#[cfg(feature="zstd")]
+extern crate zstd;
 
 pub mod asset_manager;
 pub mod ecs;
@@ -89,3 +112,14 @@ pub mod gfx_device;
 mod engine;
 
 pub use engine::*;
+
+/// Floating point type used by the core math, e.g. `LocalTransform` and
+/// `Transform`. This is `f32` by default, and `f64` with the `float64`
+/// feature for large worlds which need double precision.
+#[cfg(not(feature="float64"))]
+pub type Float = f32;
+/// Floating point type used by the core math, e.g. `LocalTransform` and
+/// `Transform`. This is `f32` by default, and `f64` with the `float64`
+/// feature for large worlds which need double precision.
+#[cfg(feature="float64")]
+pub type Float = f64;